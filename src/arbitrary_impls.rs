@@ -0,0 +1,81 @@
+//! [Arbitrary](`arbitrary::Arbitrary`) implementations of the Starknet types, for fuzzing.
+//!
+//! Most types derive their implementation. The types below are implemented manually so that the
+//! generated values respect the invariants of the types:
+//! - [PatriciaKey] and [ContractAddress] are smaller than the Patricia key upper bound.
+//! - [ResourceBoundsMapping] always contains the L1 gas and L2 gas resources.
+//! - The addresses and keys in a [ThinStateDiff] are strictly increasing.
+#[cfg(test)]
+#[path = "arbitrary_impls_test.rs"]
+mod arbitrary_impls_test;
+
+use std::collections::BTreeMap;
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+use starknet_types_core::felt::Felt;
+
+use crate::core::{ClassHash, CompiledClassHash, ContractAddress, Nonce, PatriciaKey};
+use crate::state::{StorageKey, ThinStateDiff};
+use crate::transaction::{Resource, ResourceBounds, ResourceBoundsMapping};
+
+impl<'a> Arbitrary<'a> for PatriciaKey {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut bytes = <[u8; 32]>::arbitrary(u)?;
+        // Clear the 5 most significant bits, so the key is smaller than 2^251.
+        bytes[0] &= 0x07;
+        PatriciaKey::try_from(Felt::from_bytes_be(&bytes))
+            .map_err(|_| arbitrary::Error::IncorrectFormat)
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        <[u8; 32]>::size_hint(depth)
+    }
+}
+
+impl<'a> Arbitrary<'a> for ContractAddress {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(ContractAddress(PatriciaKey::arbitrary(u)?))
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        PatriciaKey::size_hint(depth)
+    }
+}
+
+impl<'a> Arbitrary<'a> for ResourceBoundsMapping {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(ResourceBoundsMapping(BTreeMap::from([
+            (Resource::L1Gas, ResourceBounds::arbitrary(u)?),
+            (Resource::L2Gas, ResourceBounds::arbitrary(u)?),
+        ])))
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        let bounds_size_hint = ResourceBounds::size_hint(depth);
+        arbitrary::size_hint::and(bounds_size_hint, bounds_size_hint)
+    }
+}
+
+impl<'a> Arbitrary<'a> for ThinStateDiff {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        // The maps are generated sorted and then converted to index maps, keeping their order.
+        let deployed_contracts = BTreeMap::<ContractAddress, ClassHash>::arbitrary(u)?;
+        let storage_diffs =
+            BTreeMap::<ContractAddress, BTreeMap<StorageKey, Felt>>::arbitrary(u)?;
+        let declared_classes = BTreeMap::<ClassHash, CompiledClassHash>::arbitrary(u)?;
+        let deprecated_declared_classes = Vec::<ClassHash>::arbitrary(u)?;
+        let nonces = BTreeMap::<ContractAddress, Nonce>::arbitrary(u)?;
+        let replaced_classes = BTreeMap::<ContractAddress, ClassHash>::arbitrary(u)?;
+        Ok(ThinStateDiff {
+            deployed_contracts: deployed_contracts.into_iter().collect(),
+            storage_diffs: storage_diffs
+                .into_iter()
+                .map(|(address, storage_entries)| (address, storage_entries.into_iter().collect()))
+                .collect(),
+            declared_classes: declared_classes.into_iter().collect(),
+            deprecated_declared_classes,
+            nonces: nonces.into_iter().collect(),
+            replaced_classes: replaced_classes.into_iter().collect(),
+        })
+    }
+}
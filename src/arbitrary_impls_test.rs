@@ -0,0 +1,56 @@
+use arbitrary::{Arbitrary, Unstructured};
+
+use crate::core::{ChainId, ContractAddress, CONTRACT_ADDRESS_DOMAIN_SIZE};
+use crate::state::ThinStateDiff;
+use crate::transaction::{Resource, ResourceBoundsMapping, Transaction};
+use crate::transaction_hash::get_transaction_hash;
+
+const N_ITERATIONS: u64 = 200;
+const INPUT_SIZE: usize = 4096;
+
+// A deterministic pseudo-random input for the given seed (xorshift64*).
+fn fuzz_input(seed: u64) -> Vec<u8> {
+    let mut state = seed.wrapping_add(1).wrapping_mul(0x9e37_79b9_7f4a_7c15);
+    (0..INPUT_SIZE / 8)
+        .flat_map(|_| {
+            state ^= state >> 12;
+            state ^= state << 25;
+            state ^= state >> 27;
+            state.wrapping_mul(0x2545_f491_4f6c_dd1d).to_be_bytes()
+        })
+        .collect()
+}
+
+fn is_strictly_increasing<T: Ord>(items: impl Iterator<Item = T> + Clone) -> bool {
+    items.clone().zip(items.skip(1)).all(|(previous, next)| previous < next)
+}
+
+#[test]
+fn arbitrary_transactions_are_hashable() {
+    for seed in 0..N_ITERATIONS {
+        let input = fuzz_input(seed);
+        let transaction = Transaction::arbitrary(&mut Unstructured::new(&input)).unwrap();
+        get_transaction_hash(&transaction, &ChainId::Mainnet, &transaction.version()).unwrap();
+    }
+}
+
+#[test]
+fn arbitrary_values_respect_invariants() {
+    for seed in 0..N_ITERATIONS {
+        let input = fuzz_input(seed);
+        let mut u = Unstructured::new(&input);
+
+        let address = ContractAddress::arbitrary(&mut u).unwrap();
+        assert!(*address.0.key() < CONTRACT_ADDRESS_DOMAIN_SIZE);
+
+        let resource_bounds = ResourceBoundsMapping::arbitrary(&mut u).unwrap();
+        assert!(resource_bounds.0.contains_key(&Resource::L1Gas));
+        assert!(resource_bounds.0.contains_key(&Resource::L2Gas));
+
+        let state_diff = ThinStateDiff::arbitrary(&mut u).unwrap();
+        assert!(is_strictly_increasing(state_diff.deployed_contracts.keys()));
+        assert!(is_strictly_increasing(state_diff.storage_diffs.keys()));
+        assert!(is_strictly_increasing(state_diff.nonces.keys()));
+        assert!(is_strictly_increasing(state_diff.replaced_classes.keys()));
+    }
+}
@@ -5,21 +5,36 @@ mod block_test;
 use std::fmt::Display;
 
 use derive_more::Display;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use starknet_types_core::felt::Felt;
 use starknet_types_core::hash::{Poseidon, StarkHash as CoreStarkHash};
 
+use crate::block_hash::block_hash_calculator::{
+    calculate_block_commitments, calculate_block_hash, TransactionHashingData,
+    TransactionOutputForHash,
+};
 use crate::core::{
-    EventCommitment, GlobalRoot, ReceiptCommitment, SequencerContractAddress, SequencerPublicKey,
-    StateDiffCommitment, TransactionCommitment,
+    ChainId, EventCommitment, GlobalRoot, ReceiptCommitment, SequencerContractAddress,
+    SequencerPublicKey, StateDiffCommitment, TransactionCommitment,
 };
 use crate::crypto::utils::{verify_message_hash_signature, CryptoError, Signature};
 use crate::data_availability::L1DataAvailabilityMode;
 use crate::hash::StarkHash;
-use crate::serde_utils::{BytesAsHex, PrefixedBytesAsHex};
-use crate::transaction::{Transaction, TransactionHash, TransactionOutput};
+use crate::state::ThinStateDiff;
+use crate::transaction::{
+    Fee, Transaction, TransactionHash, TransactionOutput, TransactionReceipt, TransactionSignature,
+    TransactionVersion,
+};
+use crate::transaction_hash::{validate_transaction_hash, QUERY_VERSION_BASE};
+use crate::{
+    impl_from_dec_or_hex_str, impl_from_through_intermediate, impl_hex_serialized_int,
+    StarknetApiError,
+};
 
 /// A block.
 #[derive(Debug, Default, Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Block {
     // TODO: Consider renaming to BlockWithCommitments, for the header use BlockHeaderWithoutHash
     // instead of BlockHeader, and add BlockHeaderCommitments and BlockHash fields.
@@ -29,6 +44,8 @@ pub struct Block {
 
 /// A version of the Starknet protocol used when creating a block.
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct StarknetVersion(pub String);
 
 impl Default for StarknetVersion {
@@ -45,6 +62,8 @@ impl Display for StarknetVersion {
 
 /// The header of a [Block](`crate::block::Block`).
 #[derive(Debug, Default, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct BlockHeader {
     // TODO: Consider removing the block hash from the header (note it can be computed from
     // the rest of the fields.
@@ -80,6 +99,7 @@ pub struct BlockHeader {
 
 /// The header of a [Block](`crate::block::Block`) without hashing.
 #[derive(Debug, Default, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct BlockHeaderWithoutHash {
     pub parent_hash: BlockHash,
     pub block_number: BlockNumber,
@@ -92,17 +112,201 @@ pub struct BlockHeaderWithoutHash {
     pub starknet_version: StarknetVersion,
 }
 
+// A view of a [BlockHeader] that serializes all of its fields, adding the commitments that are
+// skipped by the default serialization. Missing commitments are omitted.
+#[derive(Serialize)]
+struct FullBlockHeader<'a> {
+    #[serde(flatten)]
+    header: &'a BlockHeader,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    state_diff_commitment: &'a Option<StateDiffCommitment>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    state_diff_length: &'a Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    transaction_commitment: &'a Option<TransactionCommitment>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    event_commitment: &'a Option<EventCommitment>,
+    n_transactions: &'a usize,
+    n_events: &'a usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    receipt_commitment: &'a Option<ReceiptCommitment>,
+}
+
+impl BlockHeader {
+    /// Serializes the header including the commitment fields, which the default serialization
+    /// skips. Commitments that are missing are omitted. The output can be deserialized back into
+    /// an identical header, so nodes that have the commitments can persist and re-emit them.
+    ///
+    /// Can be used as a `#[serde(serialize_with = "BlockHeader::serialize_full")]` attribute.
+    pub fn serialize_full<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        FullBlockHeader {
+            header: self,
+            state_diff_commitment: &self.state_diff_commitment,
+            state_diff_length: &self.state_diff_length,
+            transaction_commitment: &self.transaction_commitment,
+            event_commitment: &self.event_commitment,
+            n_transactions: &self.n_transactions,
+            n_events: &self.n_events,
+            receipt_commitment: &self.receipt_commitment,
+        }
+        .serialize(serializer)
+    }
+
+    /// Returns the header in the format of the RPC `BLOCK_HEADER` object.
+    pub fn to_rpc_header(&self) -> RpcBlockHeader {
+        RpcBlockHeader {
+            block_hash: self.block_hash,
+            parent_hash: self.parent_hash,
+            block_number: self.block_number,
+            new_root: self.state_root,
+            timestamp: self.timestamp,
+            sequencer_address: self.sequencer,
+            l1_gas_price: self.l1_gas_price,
+            l1_data_gas_price: self.l1_data_gas_price,
+            l1_da_mode: self.l1_da_mode,
+            starknet_version: self.starknet_version.clone(),
+        }
+    }
+
+    /// Serializes the header as an RPC `BLOCK_HEADER` object; see [BlockHeader::to_rpc_header].
+    ///
+    /// Can be used as a `#[serde(serialize_with = "BlockHeader::serialize_rpc")]` attribute.
+    pub fn serialize_rpc<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_rpc_header().serialize(serializer)
+    }
+
+    /// Deserializes the header from an RPC `BLOCK_HEADER` object; see
+    /// [BlockHeader::from_rpc_header].
+    ///
+    /// Can be used as a `#[serde(deserialize_with = "BlockHeader::deserialize_rpc")]` attribute.
+    pub fn deserialize_rpc<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        RpcBlockHeader::deserialize(deserializer).map(Self::from_rpc_header)
+    }
+
+    /// Creates a header from an RPC `BLOCK_HEADER` object. The commitment fields, which are not
+    /// part of the RPC header, are left empty.
+    pub fn from_rpc_header(rpc_header: RpcBlockHeader) -> Self {
+        Self {
+            block_hash: rpc_header.block_hash,
+            parent_hash: rpc_header.parent_hash,
+            block_number: rpc_header.block_number,
+            l1_gas_price: rpc_header.l1_gas_price,
+            l1_data_gas_price: rpc_header.l1_data_gas_price,
+            state_root: rpc_header.new_root,
+            sequencer: rpc_header.sequencer_address,
+            timestamp: rpc_header.timestamp,
+            l1_da_mode: rpc_header.l1_da_mode,
+            starknet_version: rpc_header.starknet_version,
+            ..Default::default()
+        }
+    }
+}
+
+/// A block header as defined by the RPC `BLOCK_HEADER` object.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct RpcBlockHeader {
+    pub block_hash: BlockHash,
+    pub parent_hash: BlockHash,
+    pub block_number: BlockNumber,
+    pub new_root: GlobalRoot,
+    pub timestamp: BlockTimestamp,
+    pub sequencer_address: SequencerContractAddress,
+    pub l1_gas_price: GasPricePerToken,
+    pub l1_data_gas_price: GasPricePerToken,
+    pub l1_da_mode: L1DataAvailabilityMode,
+    pub starknet_version: StarknetVersion,
+}
+
 /// The [transactions](`crate::transaction::Transaction`) and their
 /// [outputs](`crate::transaction::TransactionOutput`) in a [block](`crate::block::Block`).
 #[derive(Debug, Default, Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct BlockBody {
     pub transactions: Vec<Transaction>,
     pub transaction_outputs: Vec<TransactionOutput>,
     pub transaction_hashes: Vec<TransactionHash>,
 }
 
+impl BlockBody {
+    /// Validates that the transactions, their outputs and their hashes are aligned, i.e., of the
+    /// same length. If a chain id is given, the hashes of the transactions are validated as well.
+    pub fn validate(
+        &self,
+        block_number: BlockNumber,
+        chain_id: Option<&ChainId>,
+    ) -> Result<(), StarknetApiError> {
+        if self.transactions.len() != self.transaction_outputs.len()
+            || self.transactions.len() != self.transaction_hashes.len()
+        {
+            return Err(BlockVerificationError::BodyLengthMismatch {
+                block_number,
+                n_transactions: self.transactions.len(),
+                n_outputs: self.transaction_outputs.len(),
+                n_hashes: self.transaction_hashes.len(),
+            }
+            .into());
+        }
+        let Some(chain_id) = chain_id else {
+            return Ok(());
+        };
+        for (index, (transaction, transaction_hash)) in
+            self.transactions.iter().zip(&self.transaction_hashes).enumerate()
+        {
+            if !validate_transaction_hash(
+                transaction,
+                &block_number,
+                chain_id,
+                *transaction_hash,
+                &transaction.version(),
+            )? {
+                return Err(BlockVerificationError::TransactionHashMismatch {
+                    block_number,
+                    index,
+                    transaction_hash: *transaction_hash,
+                }
+                .into());
+            }
+        }
+        Ok(())
+    }
+
+    /// Iterates over the transactions of the body, along with their outputs and hashes. Stops at
+    /// the shortest of the three; see [BlockBody::validate].
+    pub fn iter(
+        &self,
+    ) -> impl Iterator<Item = (&Transaction, &TransactionOutput, &TransactionHash)> {
+        self.transactions
+            .iter()
+            .zip(&self.transaction_outputs)
+            .zip(&self.transaction_hashes)
+            .map(|((transaction, output), hash)| (transaction, output, hash))
+    }
+
+    /// Assembles the receipts of the transactions of the body, which is in the block with the
+    /// given hash and number, by pairing each output with the hash of its transaction.
+    pub fn receipts(
+        &self,
+        block_hash: BlockHash,
+        block_number: BlockNumber,
+    ) -> Vec<TransactionReceipt> {
+        self.transaction_outputs
+            .iter()
+            .zip(&self.transaction_hashes)
+            .map(|(output, transaction_hash)| TransactionReceipt {
+                transaction_hash: *transaction_hash,
+                block_hash,
+                block_number,
+                output: output.clone(),
+            })
+            .collect()
+    }
+}
+
 /// The status of a [Block](`crate::block::Block`).
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum BlockStatus {
     /// A pending block; i.e., a block that is yet to be closed.
     #[serde(rename = "PENDING")]
@@ -150,8 +354,27 @@ pub struct BlockHash(pub StarkHash);
     PartialOrd,
     Ord,
 )]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct BlockNumber(pub u64);
 
+impl From<u64> for BlockNumber {
+    fn from(value: u64) -> Self {
+        Self(value)
+    }
+}
+
+impl_from_through_intermediate!(u64, BlockNumber, u8, u16, u32);
+
+impl std::str::FromStr for BlockNumber {
+    type Err = StarknetApiError;
+
+    /// Parses a decimal block number.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(s.parse()?))
+    }
+}
+
 impl BlockNumber {
     /// Returns the next block number, without checking if it's in range.
     pub fn unchecked_next(&self) -> BlockNumber {
@@ -183,27 +406,73 @@ impl BlockNumber {
 #[derive(
     Debug, Copy, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord,
 )]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct GasPricePerToken {
     pub price_in_fri: GasPrice,
     pub price_in_wei: GasPrice,
 }
 
+impl GasPricePerToken {
+    /// Returns gas prices of zero in both tokens, as in devnets and genesis blocks.
+    pub const fn zero() -> Self {
+        Self::uniform(GasPrice(0))
+    }
+
+    /// Returns the same gas price in both tokens.
+    pub const fn uniform(price: GasPrice) -> Self {
+        Self { price_in_fri: price, price_in_wei: price }
+    }
+
+    /// Returns whether the gas price is zero in both tokens.
+    pub fn is_zero(&self) -> bool {
+        *self == Self::zero()
+    }
+
+    /// Returns the price that applies to a transaction of the given version: transactions from
+    /// V3 on pay their fee in STRK, i.e., in fri, and earlier transactions in ETH, i.e., in wei.
+    ///
+    /// Query versions, i.e., versions with bit 128 set for simulation, are priced as the
+    /// version they are based on.
+    pub fn price_for_version(&self, version: &TransactionVersion) -> GasPrice {
+        let base_version = if version.0 >= QUERY_VERSION_BASE {
+            version.0 - QUERY_VERSION_BASE
+        } else {
+            version.0
+        };
+        if base_version >= TransactionVersion::THREE.0 {
+            self.price_in_fri
+        } else {
+            self.price_in_wei
+        }
+    }
+}
+
 /// The gas price at a [Block](`crate::block::Block`).
-#[derive(
-    Debug, Copy, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord,
-)]
-#[serde(from = "PrefixedBytesAsHex<16_usize>", into = "PrefixedBytesAsHex<16_usize>")]
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
 pub struct GasPrice(pub u128);
 
-impl From<PrefixedBytesAsHex<16_usize>> for GasPrice {
-    fn from(val: PrefixedBytesAsHex<16_usize>) -> Self {
-        GasPrice(u128::from_be_bytes(val.0))
+impl_hex_serialized_int!(GasPrice, u128, 16);
+impl_from_dec_or_hex_str!(GasPrice: u128);
+
+impl From<u128> for GasPrice {
+    fn from(value: u128) -> Self {
+        Self(value)
     }
 }
 
-impl From<GasPrice> for PrefixedBytesAsHex<16_usize> {
-    fn from(val: GasPrice) -> Self {
-        BytesAsHex(val.0.to_be_bytes())
+impl_from_through_intermediate!(u128, GasPrice, u8, u16, u32, u64);
+
+impl GasPrice {
+    /// Returns the fee for the given amount of gas at this price, or `None` on overflow.
+    pub fn checked_mul(&self, amount: u64) -> Option<Fee> {
+        self.0.checked_mul(amount.into()).map(Fee)
+    }
+
+    /// Returns the fee for the given amount of gas at this price, saturating at [u128::MAX].
+    pub fn saturating_mul(&self, amount: u64) -> Fee {
+        Fee(self.0.saturating_mul(amount.into()))
     }
 }
 
@@ -211,6 +480,8 @@ impl From<GasPrice> for PrefixedBytesAsHex<16_usize> {
 #[derive(
     Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord,
 )]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct BlockTimestamp(pub u64);
 
 /// The signature of a [Block](`crate::block::Block`), signed by the sequencer. The signed message
@@ -218,23 +489,163 @@ pub struct BlockTimestamp(pub u64);
 #[derive(
     Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord,
 )]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct BlockSignature(pub Signature);
 
 /// The error type returned from the block verification functions.
-#[derive(thiserror::Error, Clone, Debug)]
+#[derive(thiserror::Error, Clone, Debug, PartialEq)]
 pub enum BlockVerificationError {
     #[error("Failed to verify the signature of block {block_hash}. Error: {error}")]
     BlockSignatureVerificationFailed { block_hash: BlockHash, error: CryptoError },
+    #[error(
+        "Block {block_number} has {n_transactions} transactions, {n_outputs} outputs and \
+         {n_hashes} transaction hashes."
+    )]
+    BodyLengthMismatch {
+        block_number: BlockNumber,
+        n_transactions: usize,
+        n_outputs: usize,
+        n_hashes: usize,
+    },
+    #[error("The header of block {block_number} is missing the {field}.")]
+    MissingHeaderField { block_number: BlockNumber, field: &'static str },
+    #[error("The {field} of block {block_number} is {actual:#x}, but {expected:#x} was computed.")]
+    CommitmentMismatch {
+        block_number: BlockNumber,
+        field: &'static str,
+        expected: Felt,
+        actual: Felt,
+    },
+    #[error("Invalid hash {transaction_hash} of transaction {index} in block {block_number}.")]
+    TransactionHashMismatch {
+        block_number: BlockNumber,
+        index: usize,
+        transaction_hash: TransactionHash,
+    },
+    #[error("The hash of block {block_number} is {actual}, but {expected} was computed.")]
+    BlockHashMismatch { block_number: BlockNumber, expected: BlockHash, actual: BlockHash },
+}
+
+impl Block {
+    /// Verifies the block against its header: recomputes the transaction, event, receipt and
+    /// state diff commitments, compares them, along with the counts, to the header, and verifies
+    /// the block hash. The hashes of the transactions are validated as well.
+    ///
+    /// The state diff of the block is required, as it's not part of the block. The optional
+    /// commitment fields of the header must be present, so only blocks whose hash is computed by
+    /// [calculate_block_hash] (Starknet v0.13.2 onwards) can be verified.
+    pub fn verify_commitments(
+        &self,
+        chain_id: &ChainId,
+        state_diff: &ThinStateDiff,
+    ) -> Result<(), StarknetApiError> {
+        let header = &self.header;
+        let body = &self.body;
+        let block_number = header.block_number;
+        body.validate(block_number, Some(chain_id))?;
+        let transactions_data = body
+            .iter()
+            .map(|(transaction, output, transaction_hash)| TransactionHashingData {
+                transaction_signature: transaction_signature(transaction),
+                transaction_output: TransactionOutputForHash::from(output),
+                transaction_hash: *transaction_hash,
+            })
+            .collect::<Vec<_>>();
+
+        let commitments =
+            calculate_block_commitments(&transactions_data, state_diff, header.l1_da_mode);
+        let n_events: usize =
+            body.transaction_outputs.iter().map(TransactionOutput::event_count).sum();
+        let missing = |field| BlockVerificationError::MissingHeaderField { block_number, field };
+        let compare = |field, expected: Felt, actual: Felt| {
+            if expected == actual {
+                Ok(())
+            } else {
+                Err(BlockVerificationError::CommitmentMismatch {
+                    block_number,
+                    field,
+                    expected,
+                    actual,
+                })
+            }
+        };
+        compare(
+            "transaction commitment",
+            commitments.transaction_commitment.0,
+            header.transaction_commitment.ok_or(missing("transaction commitment"))?.0,
+        )?;
+        compare(
+            "event commitment",
+            commitments.event_commitment.0,
+            header.event_commitment.ok_or(missing("event commitment"))?.0,
+        )?;
+        compare(
+            "receipt commitment",
+            commitments.receipt_commitment.0,
+            header.receipt_commitment.ok_or(missing("receipt commitment"))?.0,
+        )?;
+        compare(
+            "state diff commitment",
+            commitments.state_diff_commitment.0.0,
+            header.state_diff_commitment.clone().ok_or(missing("state diff commitment"))?.0.0,
+        )?;
+        compare(
+            "state diff length",
+            Felt::from(state_diff.len()),
+            Felt::from(header.state_diff_length.ok_or(missing("state diff length"))?),
+        )?;
+        compare(
+            "transaction count",
+            Felt::from(body.transactions.len()),
+            Felt::from(header.n_transactions),
+        )?;
+        compare("event count", Felt::from(n_events), Felt::from(header.n_events))?;
+
+        let block_hash = calculate_block_hash(
+            BlockHeaderWithoutHash {
+                parent_hash: header.parent_hash,
+                block_number,
+                l1_gas_price: header.l1_gas_price,
+                l1_data_gas_price: header.l1_data_gas_price,
+                state_root: header.state_root,
+                sequencer: header.sequencer,
+                timestamp: header.timestamp,
+                l1_da_mode: header.l1_da_mode,
+                starknet_version: header.starknet_version.clone(),
+            },
+            commitments,
+        );
+        if block_hash != header.block_hash {
+            return Err(BlockVerificationError::BlockHashMismatch {
+                block_number,
+                expected: block_hash,
+                actual: header.block_hash,
+            }
+            .into());
+        }
+        Ok(())
+    }
+}
+
+// The signature of a transaction as it's committed to, or None for transaction types without a
+// signature.
+fn transaction_signature(transaction: &Transaction) -> Option<TransactionSignature> {
+    match transaction {
+        Transaction::Declare(tx) => Some(tx.signature()),
+        Transaction::DeployAccount(tx) => Some(tx.signature()),
+        Transaction::Invoke(tx) => Some(tx.signature()),
+        Transaction::Deploy(_) | Transaction::L1Handler(_) => None,
+    }
 }
 
 /// Verifies that the the block header was signed by the expected sequencer.
 pub fn verify_block_signature(
     sequencer_pub_key: &SequencerPublicKey,
     signature: &BlockSignature,
-    state_diff_commitment: &GlobalRoot,
+    state_diff_commitment: &StateDiffCommitment,
     block_hash: &BlockHash,
 ) -> Result<bool, BlockVerificationError> {
-    let message_hash = Poseidon::hash_array(&[block_hash.0, state_diff_commitment.0]);
+    let message_hash = Poseidon::hash_array(&[block_hash.0, state_diff_commitment.0.0]);
     verify_message_hash_signature(&message_hash, &signature.0, &sequencer_pub_key.0).map_err(
         |err| BlockVerificationError::BlockSignatureVerificationFailed {
             block_hash: *block_hash,
@@ -242,3 +653,19 @@ pub fn verify_block_signature(
         },
     )
 }
+
+/// Verifies that the block header was signed by the expected sequencer. The block hash and the
+/// state diff commitment are taken from the header; the latter must be present.
+pub fn verify_block_header_signature(
+    header: &BlockHeader,
+    signature: &BlockSignature,
+    sequencer_pub_key: &SequencerPublicKey,
+) -> Result<bool, BlockVerificationError> {
+    let state_diff_commitment = header.state_diff_commitment.as_ref().ok_or(
+        BlockVerificationError::MissingHeaderField {
+            block_number: header.block_number,
+            field: "state diff commitment",
+        },
+    )?;
+    verify_block_signature(sequencer_pub_key, signature, state_diff_commitment, &header.block_hash)
+}
@@ -8,22 +8,25 @@ use super::receipt_commitment::{calculate_receipt_commitment, ReceiptElement};
 use super::state_diff_hash::calculate_state_diff_hash;
 use super::transaction_commitment::{calculate_transaction_commitment, TransactionLeafElement};
 use crate::block::{BlockHash, BlockHeaderWithoutHash};
-use crate::core::{EventCommitment, ReceiptCommitment, StateDiffCommitment, TransactionCommitment};
+use crate::core::{
+    encode_shortstring, EventCommitment, ReceiptCommitment, StateDiffCommitment,
+    TransactionCommitment,
+};
 use crate::crypto::utils::HashChain;
 use crate::data_availability::L1DataAvailabilityMode;
 use crate::state::ThinStateDiff;
 use crate::transaction::{
     Event, Fee, GasVector, MessageToL1, TransactionExecutionStatus, TransactionHash,
-    TransactionSignature,
+    TransactionOutput, TransactionSignature,
 };
-use crate::transaction_hash::ascii_as_felt;
 
 #[cfg(test)]
 #[path = "block_hash_calculator_test.rs"]
 mod block_hash_calculator_test;
 
 static STARKNET_BLOCK_HASH0: Lazy<Felt> = Lazy::new(|| {
-    ascii_as_felt("STARKNET_BLOCK_HASH0").expect("ascii_as_felt failed for 'STARKNET_BLOCK_HASH0'")
+    encode_shortstring("STARKNET_BLOCK_HASH0")
+        .expect("encode_shortstring failed for 'STARKNET_BLOCK_HASH0'")
 });
 
 /// The common fields of transaction output types.
@@ -36,6 +39,18 @@ pub struct TransactionOutputForHash {
     pub messages_sent: Vec<MessageToL1>,
 }
 
+impl From<&TransactionOutput> for TransactionOutputForHash {
+    fn from(output: &TransactionOutput) -> Self {
+        Self {
+            actual_fee: output.actual_fee(),
+            events: output.events().to_vec(),
+            execution_status: output.execution_status().clone(),
+            gas_consumed: output.execution_resources().gas_consumed.clone(),
+            messages_sent: output.messages_sent().clone(),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
 pub struct TransactionHashingData {
     pub transaction_signature: Option<TransactionSignature>,
@@ -68,7 +83,7 @@ pub fn calculate_block_hash(
             .chain(&STARKNET_BLOCK_HASH0)
             .chain(&header.block_number.0.into())
             .chain(&header.state_root.0)
-            .chain(&header.sequencer.0)
+            .chain(header.sequencer.as_contract_address())
             .chain(&header.timestamp.0.into())
             .chain(&block_commitments.concatenated_counts)
             .chain(&block_commitments.state_diff_commitment.0.0)
@@ -79,7 +94,7 @@ pub fn calculate_block_hash(
             .chain(&header.l1_gas_price.price_in_fri.0.into())
             .chain(&header.l1_data_gas_price.price_in_wei.0.into())
             .chain(&header.l1_data_gas_price.price_in_fri.0.into())
-            .chain(&ascii_as_felt(&header.starknet_version.0).expect("Expect ASCII version"))
+            .chain(&encode_shortstring(&header.starknet_version.0).expect("Expect ASCII version"))
             .chain(&Felt::ZERO)
             .chain(&header.parent_hash.0)
             .get_poseidon_hash(),
@@ -137,15 +152,11 @@ fn concat_counts(
     state_diff_length: usize,
     l1_data_availability_mode: L1DataAvailabilityMode,
 ) -> Felt {
-    let l1_data_availability_byte: u8 = match l1_data_availability_mode {
-        L1DataAvailabilityMode::Calldata => 0,
-        L1DataAvailabilityMode::Blob => 0b10000000,
-    };
     let concat_bytes = [
         to_64_bits(transaction_count).as_slice(),
         to_64_bits(event_count).as_slice(),
         to_64_bits(state_diff_length).as_slice(),
-        &[l1_data_availability_byte],
+        &[l1_data_availability_mode.as_commitment_byte()],
         &[0_u8; 7], // zero padding
     ]
     .concat();
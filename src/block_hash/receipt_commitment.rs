@@ -37,11 +37,12 @@ pub fn calculate_receipt_commitment<H: StarkHash>(
     ))
 }
 
-// Poseidon(
-//    transaction hash, amount of fee paid, hash of messages sent, revert reason,
-//    execution resources
-// ).
-fn calculate_receipt_hash(receipt_element: &ReceiptElement) -> Felt {
+/// Returns the hash of a receipt, i.e., a leaf in the receipts Patricia tree:
+/// Poseidon(
+///    transaction hash, amount of fee paid, hash of messages sent, revert reason,
+///    execution resources
+/// ).
+pub fn calculate_receipt_hash(receipt_element: &ReceiptElement) -> Felt {
     let hash_chain = HashChain::new()
         .chain(&receipt_element.transaction_hash)
         .chain(&receipt_element.transaction_output.actual_fee.0.into())
@@ -72,7 +73,7 @@ fn get_revert_reason_hash(execution_status: &TransactionExecutionStatus) -> Felt
     match execution_status {
         TransactionExecutionStatus::Succeeded => Felt::ZERO,
         TransactionExecutionStatus::Reverted(reason) => {
-            starknet_keccak_hash(reason.revert_reason.as_bytes())
+            starknet_keccak_hash(reason.revert_reason_bytes())
         }
     }
 }
@@ -2,6 +2,7 @@ use starknet_types_core::felt::Felt;
 use starknet_types_core::hash::Poseidon;
 
 use super::calculate_messages_sent_hash;
+use crate::block::{BlockHash, BlockNumber};
 use crate::block_hash::receipt_commitment::{
     calculate_receipt_commitment, calculate_receipt_hash, get_revert_reason_hash, ReceiptElement,
 };
@@ -9,7 +10,8 @@ use crate::block_hash::test_utils::{generate_message_to_l1, get_transaction_outp
 use crate::core::ReceiptCommitment;
 use crate::felt;
 use crate::transaction::{
-    RevertedTransactionExecutionStatus, TransactionExecutionStatus, TransactionHash,
+    ExecutionResources, Fee, InvokeTransactionOutput, RevertedTransactionExecutionStatus,
+    TransactionExecutionStatus, TransactionHash, TransactionOutput, TransactionReceipt,
 };
 
 #[test]
@@ -47,3 +49,50 @@ fn test_revert_reason_hash_regression() {
     let expected_hash = felt!("0x01629b9dda060bb30c7908346f6af189c16773fa148d3366701fbaa35d54f3c8");
     assert_eq!(get_revert_reason_hash(&execution_reverted), expected_hash);
 }
+
+#[test]
+fn test_receipt_for_commitment() {
+    let output_for_hash = get_transaction_output();
+    let receipt = TransactionReceipt {
+        transaction_hash: TransactionHash(Felt::from(1234_u16)),
+        block_hash: BlockHash(felt!("0x5")),
+        block_number: BlockNumber(6),
+        output: TransactionOutput::Invoke(InvokeTransactionOutput {
+            actual_fee: output_for_hash.actual_fee,
+            messages_sent: output_for_hash.messages_sent.clone(),
+            events: output_for_hash.events.clone(),
+            execution_status: output_for_hash.execution_status.clone(),
+            execution_resources: ExecutionResources {
+                steps: 98,
+                gas_consumed: output_for_hash.gas_consumed.clone(),
+                ..Default::default()
+            },
+        }),
+    };
+
+    let receipt_element = receipt.for_commitment();
+    assert_eq!(receipt_element.transaction_hash, receipt.transaction_hash);
+    assert_eq!(receipt_element.transaction_output, output_for_hash);
+    // Same as in the receipt hash regression test.
+    let expected_hash = felt!("0x6276abf21e7c68b2eecfdc8a845b11b44401901f5f040efe10c60d625049646");
+    assert_eq!(calculate_receipt_hash(&receipt_element), expected_hash);
+}
+
+// The gas consumed is hashed as is, rather than derived from the fee and the gas prices, so a zero
+// fee (e.g., a zero gas price on a devnet) can't underflow or divide by zero.
+#[test]
+fn test_receipt_hash_with_zero_fee() {
+    let mut transaction_output = get_transaction_output();
+    transaction_output.actual_fee = Fee(0);
+    let receipt_element = ReceiptElement {
+        transaction_hash: TransactionHash(Felt::from(1234_u16)),
+        transaction_output,
+    };
+    let expected_hash = felt!("0x49f659bfba0a2e6b11e2090b8701d310a0d5b6c0c2c75c1cbc0565d86d4dd6a");
+    assert_eq!(calculate_receipt_hash(&receipt_element), expected_hash);
+
+    let expected_root = ReceiptCommitment(felt!(
+        "0x3f633bd548172ddf063d0fc361dcbfb9384246d5297b1c8538319ce97a7ced2"
+    ));
+    assert_eq!(calculate_receipt_commitment::<Poseidon>(&[receipt_element]), expected_root);
+}
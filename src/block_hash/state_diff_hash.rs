@@ -2,18 +2,20 @@ use indexmap::IndexMap;
 use once_cell::sync::Lazy;
 use starknet_types_core::felt::Felt;
 
-use crate::core::{ClassHash, CompiledClassHash, ContractAddress, Nonce, StateDiffCommitment};
+use crate::core::{
+    encode_shortstring, ClassHash, CompiledClassHash, ContractAddress, Nonce, StateDiffCommitment,
+};
 use crate::crypto::utils::HashChain;
 use crate::hash::PoseidonHash;
 use crate::state::{StorageKey, ThinStateDiff};
-use crate::transaction_hash::ascii_as_felt;
 
 #[cfg(test)]
 #[path = "state_diff_hash_test.rs"]
 mod state_diff_hash_test;
 
 static STARKNET_STATE_DIFF0: Lazy<Felt> = Lazy::new(|| {
-    ascii_as_felt("STARKNET_STATE_DIFF0").expect("ascii_as_felt failed for 'STARKNET_STATE_DIFF0'")
+    encode_shortstring("STARKNET_STATE_DIFF0")
+        .expect("encode_shortstring failed for 'STARKNET_STATE_DIFF0'")
 });
 
 /// Poseidon(
@@ -39,15 +41,17 @@ pub fn calculate_state_diff_hash(state_diff: &ThinStateDiff) -> StateDiffCommitm
 }
 
 // Chains: [number_of_updated_contracts, address_0, class_hash_0, address_1, class_hash_1, ...].
-// The updated contracts includes deployed contracts and replaced classes.
+// The updated contracts includes deployed contracts and replaced classes. A contract that was
+// both deployed and replaced appears once, with its replaced class hash.
 fn chain_updated_contracts(
     deployed_contracts: &IndexMap<ContractAddress, ClassHash>,
     replaced_classes: &IndexMap<ContractAddress, ClassHash>,
     mut hash_chain: HashChain,
 ) -> HashChain {
-    let updated_contracts = deployed_contracts.iter().chain(replaced_classes.iter());
-    hash_chain = hash_chain.chain(&(deployed_contracts.len() + replaced_classes.len()).into());
-    for (address, class_hash) in sorted_index_map(&updated_contracts.collect()) {
+    let updated_contracts: IndexMap<_, _> =
+        deployed_contracts.iter().chain(replaced_classes.iter()).collect();
+    hash_chain = hash_chain.chain(&updated_contracts.len().into());
+    for (address, class_hash) in sorted_index_map(&updated_contracts) {
         hash_chain = hash_chain.chain(&address.0).chain(class_hash);
     }
     hash_chain
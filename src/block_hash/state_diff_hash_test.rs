@@ -7,8 +7,10 @@ use crate::block_hash::state_diff_hash::{
 use crate::block_hash::test_utils::get_state_diff;
 use crate::core::{ClassHash, CompiledClassHash, Nonce, StateDiffCommitment};
 use crate::crypto::utils::HashChain;
+use crate::deprecated_contract_class::ContractClass as DeprecatedContractClass;
 use crate::felt;
 use crate::hash::PoseidonHash;
+use crate::state::{ContractClass, StateDiff, ThinStateDiff};
 
 #[test]
 fn test_state_diff_hash_regression() {
@@ -21,6 +23,39 @@ fn test_state_diff_hash_regression() {
     assert_eq!(expected_hash, calculate_state_diff_hash(&state_diff));
 }
 
+#[test]
+fn test_replaced_classes_affect_hash() {
+    let mut state_diff = get_state_diff();
+    let hash = calculate_state_diff_hash(&state_diff);
+
+    state_diff.replaced_classes.insert(21u64.into(), ClassHash(22u64.into()));
+    assert_ne!(hash, calculate_state_diff_hash(&state_diff));
+}
+
+#[test]
+fn test_updated_contracts_deduplicated_by_address() {
+    let deployed_contracts = indexmap! {
+        0u64.into() => ClassHash(1u64.into()),
+        2u64.into() => ClassHash(3u64.into()),
+    };
+    let replaced_classes = indexmap! {
+        2u64.into() => ClassHash(4u64.into()),
+    };
+
+    // The replaced class hash of the deployed contract is the one that's hashed.
+    let expected_hash_chain = HashChain::new()
+        .chain(&2u64.into())
+        .chain(&0u64.into())
+        .chain(&1u64.into())
+        .chain(&2u64.into())
+        .chain(&4u64.into());
+    assert_eq!(
+        chain_updated_contracts(&deployed_contracts, &replaced_classes, HashChain::new())
+            .get_poseidon_hash(),
+        expected_hash_chain.get_poseidon_hash(),
+    );
+}
+
 #[test]
 fn test_sorting_deployed_contracts() {
     let deployed_contracts_0 = indexmap! {
@@ -134,3 +169,29 @@ fn test_sorting_nonces() {
         chain_nonces(&nonces_1, HashChain::new()).get_poseidon_hash(),
     );
 }
+
+#[test]
+fn test_state_diff_commitment_of_full_state_diff() {
+    let thin_state_diff = get_state_diff();
+    let state_diff = StateDiff {
+        deployed_contracts: thin_state_diff.deployed_contracts.clone(),
+        storage_diffs: thin_state_diff.storage_diffs.clone(),
+        declared_classes: thin_state_diff
+            .declared_classes
+            .iter()
+            .map(|(class_hash, compiled_class_hash)| {
+                (*class_hash, (*compiled_class_hash, ContractClass::default()))
+            })
+            .collect(),
+        deprecated_declared_classes: thin_state_diff
+            .deprecated_declared_classes
+            .iter()
+            .map(|class_hash| (*class_hash, DeprecatedContractClass::default()))
+            .collect(),
+        nonces: thin_state_diff.nonces.clone(),
+        replaced_classes: thin_state_diff.replaced_classes.clone(),
+    };
+
+    assert_eq!(ThinStateDiff::from(&state_diff), thin_state_diff);
+    assert_eq!(state_diff.commitment(), calculate_state_diff_hash(&thin_state_diff));
+}
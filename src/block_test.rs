@@ -1,8 +1,34 @@
-use super::verify_block_signature;
-use crate::block::{BlockHash, BlockNumber, BlockSignature};
-use crate::core::{GlobalRoot, SequencerPublicKey};
+use assert_matches::assert_matches;
+use serde::{Deserialize, Serialize};
+use starknet_types_core::felt::Felt;
+use starknet_types_core::hash::Poseidon;
+
+use super::{verify_block_header_signature, verify_block_signature, BlockVerificationError};
+use crate::block::{
+    Block, BlockBody, BlockHash, BlockHeader, BlockHeaderWithoutHash, BlockNumber, BlockSignature,
+    BlockTimestamp, GasPrice, GasPricePerToken, RpcBlockHeader, StarknetVersion,
+};
+use crate::block_hash::block_hash_calculator::{
+    calculate_block_commitments, calculate_block_hash, TransactionHashingData,
+    TransactionOutputForHash,
+};
+use crate::block_hash::receipt_commitment::calculate_receipt_commitment;
+use crate::block_hash::test_utils::get_state_diff;
+use crate::core::{
+    ChainId, ContractAddress, EventCommitment, GlobalRoot, Nonce, PatriciaKey, ReceiptCommitment,
+    SequencerContractAddress, SequencerPublicKey, StateDiffCommitment, TransactionCommitment,
+};
 use crate::crypto::utils::{PublicKey, Signature};
-use crate::felt;
+use crate::data_availability::L1DataAvailabilityMode;
+use crate::hash::PoseidonHash;
+use crate::state::ThinStateDiff;
+use crate::transaction::{
+    Calldata, Event, EventContent, EventData, EventKey, Fee, InvokeTransaction,
+    InvokeTransactionOutput, InvokeTransactionV1, Transaction, TransactionHash, TransactionOutput,
+    TransactionReceipt, TransactionSignature, TransactionVersion,
+};
+use crate::transaction_hash::{get_transaction_hash, QUERY_VERSION_BASE};
+use crate::{calldata, contract_address, felt, patricia_key, StarknetApiError};
 
 #[test]
 fn test_block_number_iteration() {
@@ -30,8 +56,9 @@ fn block_signature_verification() {
     // Values taken from Mainnet.
     let block_hash =
         BlockHash(felt!("0x7d5db04c5ca2aea828180dc441afb1580e3cee7547a3567ced3aa5bb8b273c0"));
-    let state_commitment =
-        GlobalRoot(felt!("0x64689c12248e1110af4b3af0e2b43cd51ad13e8855f10e37669e2a4baf919c6"));
+    let state_diff_commitment = StateDiffCommitment(PoseidonHash(felt!(
+        "0x64689c12248e1110af4b3af0e2b43cd51ad13e8855f10e37669e2a4baf919c6"
+    )));
     let signature = BlockSignature(Signature {
         r: felt!("0x1b382bbfd693011c9b7692bc932b23ed9c288deb27c8e75772e172abbe5950c"),
         s: felt!("0xbe4438085057e1a7c704a0da3b30f7b8340fe3d24c86772abfd24aa597e42"),
@@ -41,7 +68,408 @@ fn block_signature_verification() {
     )));
 
     assert!(
-        verify_block_signature(&sequencer_pub_key, &signature, &state_commitment, &block_hash)
+        verify_block_signature(&sequencer_pub_key, &signature, &state_diff_commitment, &block_hash)
+            .unwrap()
+    );
+
+    // A signature over a different state diff commitment doesn't verify.
+    let other_commitment = StateDiffCommitment(PoseidonHash(Felt::ONE));
+    assert!(
+        !verify_block_signature(&sequencer_pub_key, &signature, &other_commitment, &block_hash)
             .unwrap()
     );
 }
+
+#[test]
+fn block_header_signature_verification() {
+    // Values taken from Mainnet.
+    let mut header = BlockHeader {
+        block_hash: BlockHash(felt!(
+            "0x7d5db04c5ca2aea828180dc441afb1580e3cee7547a3567ced3aa5bb8b273c0"
+        )),
+        block_number: BlockNumber(635000),
+        state_diff_commitment: Some(StateDiffCommitment(PoseidonHash(felt!(
+            "0x64689c12248e1110af4b3af0e2b43cd51ad13e8855f10e37669e2a4baf919c6"
+        )))),
+        ..Default::default()
+    };
+    let signature = BlockSignature(Signature {
+        r: felt!("0x1b382bbfd693011c9b7692bc932b23ed9c288deb27c8e75772e172abbe5950c"),
+        s: felt!("0xbe4438085057e1a7c704a0da3b30f7b8340fe3d24c86772abfd24aa597e42"),
+    });
+    let sequencer_pub_key = SequencerPublicKey(PublicKey(felt!(
+        "0x48253ff2c3bed7af18bde0b611b083b39445959102d4947c51c4db6aa4f4e58"
+    )));
+
+    assert!(verify_block_header_signature(&header, &signature, &sequencer_pub_key).unwrap());
+
+    header.state_diff_commitment = None;
+    assert_matches!(
+        verify_block_header_signature(&header, &signature, &sequencer_pub_key),
+        Err(BlockVerificationError::MissingHeaderField { field: "state diff commitment", .. })
+    );
+}
+
+// A block header as returned by an RPC node.
+fn rpc_header_json() -> serde_json::Value {
+    serde_json::json!({
+        "block_hash": "0x7d5db04c5ca2aea828180dc441afb1580e3cee7547a3567ced3aa5bb8b273c0",
+        "parent_hash": "0x6a8e7bb3e1a2e4b7ea3e3c1aef84c82c5a1e5e2a1d1e3a5d94f6f1a2b3c4d5e",
+        "block_number": 635000,
+        "new_root": "0x64689c12248e1110af4b3af0e2b43cd51ad13e8855f10e37669e2a4baf919c6",
+        "timestamp": 1713261013,
+        "sequencer_address": "0x1176a1bd84444c89232ec27754698e5d2e7e1a7f1539f12027f28b23ec9f3d8",
+        "l1_gas_price": { "price_in_fri": "0x1d1a94a20000", "price_in_wei": "0x2540be400" },
+        "l1_data_gas_price": { "price_in_fri": "0x186a0", "price_in_wei": "0x1" },
+        "l1_da_mode": "BLOB",
+        "starknet_version": "0.13.1.1"
+    })
+}
+
+#[test]
+fn block_header_rpc_round_trip() {
+    let rpc_header_json = rpc_header_json();
+    let rpc_header: RpcBlockHeader = serde_json::from_value(rpc_header_json.clone()).unwrap();
+
+    let header = BlockHeader::from_rpc_header(rpc_header.clone());
+    let expected_header = BlockHeader {
+        block_hash: BlockHash(felt!(
+            "0x7d5db04c5ca2aea828180dc441afb1580e3cee7547a3567ced3aa5bb8b273c0"
+        )),
+        parent_hash: BlockHash(felt!(
+            "0x6a8e7bb3e1a2e4b7ea3e3c1aef84c82c5a1e5e2a1d1e3a5d94f6f1a2b3c4d5e"
+        )),
+        block_number: BlockNumber(635000),
+        l1_gas_price: GasPricePerToken {
+            price_in_fri: GasPrice(0x1d1a94a20000),
+            price_in_wei: GasPrice(0x2540be400),
+        },
+        l1_data_gas_price: GasPricePerToken {
+            price_in_fri: GasPrice(0x186a0),
+            price_in_wei: GasPrice(0x1),
+        },
+        state_root: GlobalRoot(felt!(
+            "0x64689c12248e1110af4b3af0e2b43cd51ad13e8855f10e37669e2a4baf919c6"
+        )),
+        sequencer: SequencerContractAddress(contract_address!(
+            "0x1176a1bd84444c89232ec27754698e5d2e7e1a7f1539f12027f28b23ec9f3d8"
+        )),
+        timestamp: BlockTimestamp(1713261013),
+        l1_da_mode: L1DataAvailabilityMode::Blob,
+        starknet_version: StarknetVersion("0.13.1.1".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(header, expected_header);
+    assert_eq!(header.to_rpc_header(), rpc_header);
+    assert_eq!(serde_json::to_value(header.to_rpc_header()).unwrap(), rpc_header_json);
+
+    // Commitment fields are not part of the RPC header.
+    let header_with_commitments = BlockHeader {
+        state_diff_commitment: Some(StateDiffCommitment(PoseidonHash(felt!("0x1")))),
+        n_transactions: 3,
+        ..expected_header
+    };
+    assert_eq!(header_with_commitments.to_rpc_header(), rpc_header);
+}
+
+#[test]
+fn block_header_serde_with_rpc() {
+    #[derive(Debug, Deserialize, Serialize)]
+    struct BlockWithRpcHeader {
+        #[serde(
+            serialize_with = "BlockHeader::serialize_rpc",
+            deserialize_with = "BlockHeader::deserialize_rpc"
+        )]
+        header: BlockHeader,
+    }
+
+    let json = serde_json::json!({ "header": rpc_header_json() });
+    let block: BlockWithRpcHeader = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(
+        block.header.sequencer.0,
+        contract_address!("0x1176a1bd84444c89232ec27754698e5d2e7e1a7f1539f12027f28b23ec9f3d8")
+    );
+    assert_eq!(block.header.l1_da_mode, L1DataAvailabilityMode::Blob);
+    assert_eq!(serde_json::to_value(&block).unwrap(), json);
+
+    // The commitments are dropped.
+    let header = BlockHeader { n_transactions: 3, ..block.header };
+    let block = BlockWithRpcHeader { header };
+    assert_eq!(serde_json::to_value(block).unwrap(), json);
+}
+
+#[test]
+fn block_header_serialize_full_round_trip() {
+    let header = BlockHeader {
+        block_hash: BlockHash(felt!("0x1")),
+        parent_hash: BlockHash(felt!("0x2")),
+        block_number: BlockNumber(3),
+        state_root: GlobalRoot(felt!("0x4")),
+        timestamp: BlockTimestamp(5),
+        l1_da_mode: L1DataAvailabilityMode::Blob,
+        state_diff_commitment: Some(StateDiffCommitment(PoseidonHash(felt!("0x6")))),
+        state_diff_length: Some(7),
+        transaction_commitment: Some(TransactionCommitment(felt!("0x8"))),
+        event_commitment: Some(EventCommitment(felt!("0x9"))),
+        n_transactions: 10,
+        n_events: 11,
+        receipt_commitment: Some(ReceiptCommitment(felt!("0xc"))),
+        starknet_version: StarknetVersion("0.13.2".to_string()),
+        ..Default::default()
+    };
+
+    let serialized = header.serialize_full(serde_json::value::Serializer).unwrap();
+    assert_eq!(serialized["state_diff_length"], 7);
+    assert_eq!(serde_json::from_value::<BlockHeader>(serialized).unwrap(), header);
+
+    // The default serialization drops the commitments.
+    let serialized = serde_json::to_value(&header).unwrap();
+    assert!(serialized.get("transaction_commitment").is_none());
+
+    // Missing commitments are omitted.
+    let header = BlockHeader { receipt_commitment: None, ..header };
+    let serialized = header.serialize_full(serde_json::value::Serializer).unwrap();
+    assert!(serialized.get("receipt_commitment").is_none());
+    assert_eq!(serde_json::from_value::<BlockHeader>(serialized).unwrap(), header);
+}
+
+fn block_with_commitments(chain_id: &ChainId, state_diff: &ThinStateDiff) -> Block {
+    let signature = TransactionSignature(vec![felt!("0x1"), felt!("0x2")]);
+    let transaction = Transaction::Invoke(InvokeTransaction::V1(InvokeTransactionV1 {
+        max_fee: Fee(1000),
+        signature: signature.clone(),
+        nonce: Nonce(felt!("0x3")),
+        sender_address: contract_address!("0x4"),
+        calldata: calldata![felt!("0x5")],
+    }));
+    let transaction_hash =
+        get_transaction_hash(&transaction, chain_id, &transaction.version()).unwrap();
+    let transaction_output = TransactionOutput::Invoke(InvokeTransactionOutput {
+        actual_fee: Fee(99804),
+        events: vec![Event {
+            from_address: contract_address!("0x6"),
+            content: EventContent {
+                keys: vec![EventKey(felt!("0x7"))],
+                data: EventData(vec![felt!("0x8")]),
+            },
+        }],
+        ..Default::default()
+    });
+    let transactions_data = vec![TransactionHashingData {
+        transaction_signature: Some(signature),
+        transaction_output: TransactionOutputForHash::from(&transaction_output),
+        transaction_hash,
+    }];
+
+    let header_without_hash = BlockHeaderWithoutHash {
+        block_number: BlockNumber(1),
+        l1_da_mode: L1DataAvailabilityMode::Blob,
+        starknet_version: StarknetVersion("0.13.2".to_string()),
+        ..Default::default()
+    };
+    let commitments = calculate_block_commitments(
+        &transactions_data,
+        state_diff,
+        header_without_hash.l1_da_mode,
+    );
+    let block_hash = calculate_block_hash(header_without_hash.clone(), commitments.clone());
+    Block {
+        header: BlockHeader {
+            block_hash,
+            block_number: header_without_hash.block_number,
+            l1_da_mode: header_without_hash.l1_da_mode,
+            starknet_version: header_without_hash.starknet_version,
+            state_diff_commitment: Some(commitments.state_diff_commitment),
+            state_diff_length: Some(state_diff.len()),
+            transaction_commitment: Some(commitments.transaction_commitment),
+            event_commitment: Some(commitments.event_commitment),
+            receipt_commitment: Some(commitments.receipt_commitment),
+            n_transactions: 1,
+            n_events: 1,
+            ..Default::default()
+        },
+        body: BlockBody {
+            transactions: vec![transaction],
+            transaction_outputs: vec![transaction_output],
+            transaction_hashes: vec![transaction_hash],
+        },
+    }
+}
+
+#[test]
+fn block_verify_commitments() {
+    let chain_id = ChainId::Mainnet;
+    let state_diff = get_state_diff();
+    let block = block_with_commitments(&chain_id, &state_diff);
+    block.verify_commitments(&chain_id, &state_diff).unwrap();
+
+    let mut tampered = block.clone();
+    tampered.body.transaction_hashes[0] = TransactionHash(felt!("0x1234"));
+    assert_matches!(
+        tampered.verify_commitments(&chain_id, &state_diff),
+        Err(StarknetApiError::BlockVerification(
+            BlockVerificationError::TransactionHashMismatch { index: 0, .. }
+        ))
+    );
+
+    let mut tampered = block.clone();
+    tampered.body.transaction_outputs.clear();
+    assert_matches!(
+        tampered.verify_commitments(&chain_id, &state_diff),
+        Err(StarknetApiError::BlockVerification(BlockVerificationError::BodyLengthMismatch {
+            n_outputs: 0,
+            ..
+        }))
+    );
+
+    let mut tampered = block.clone();
+    tampered.header.receipt_commitment = None;
+    assert_matches!(
+        tampered.verify_commitments(&chain_id, &state_diff),
+        Err(StarknetApiError::BlockVerification(BlockVerificationError::MissingHeaderField {
+            field: "receipt commitment",
+            ..
+        }))
+    );
+
+    let mut tampered = block.clone();
+    tampered.header.n_events = 2;
+    assert_matches!(
+        tampered.verify_commitments(&chain_id, &state_diff),
+        Err(StarknetApiError::BlockVerification(BlockVerificationError::CommitmentMismatch {
+            field: "event count",
+            ..
+        }))
+    );
+
+    // A different state diff changes the state diff commitment.
+    assert_matches!(
+        block.verify_commitments(&chain_id, &ThinStateDiff::default()),
+        Err(StarknetApiError::BlockVerification(BlockVerificationError::CommitmentMismatch {
+            field: "state diff commitment",
+            ..
+        }))
+    );
+
+    let mut tampered = block;
+    tampered.header.timestamp = BlockTimestamp(1);
+    assert_matches!(
+        tampered.verify_commitments(&chain_id, &state_diff),
+        Err(StarknetApiError::BlockVerification(BlockVerificationError::BlockHashMismatch { .. }))
+    );
+}
+
+#[test]
+fn gas_price_per_token_helpers() {
+    let zero = GasPricePerToken::zero();
+    assert_eq!(zero, GasPricePerToken::default());
+    assert!(zero.is_zero());
+
+    let uniform = GasPricePerToken::uniform(GasPrice(3));
+    assert_eq!(uniform, GasPricePerToken { price_in_fri: GasPrice(3), price_in_wei: GasPrice(3) });
+    assert!(!uniform.is_zero());
+    assert!(!GasPricePerToken { price_in_fri: GasPrice(0), price_in_wei: GasPrice(1) }.is_zero());
+    assert_eq!(GasPricePerToken::uniform(GasPrice(0)), zero);
+}
+
+#[test]
+fn gas_price_for_version() {
+    let prices = GasPricePerToken { price_in_fri: GasPrice(5), price_in_wei: GasPrice(7) };
+    assert_eq!(prices.price_for_version(&TransactionVersion::ONE), GasPrice(7));
+    assert_eq!(prices.price_for_version(&TransactionVersion::TWO), GasPrice(7));
+    assert_eq!(prices.price_for_version(&TransactionVersion::THREE), GasPrice(5));
+
+    // Query versions are priced as their base version.
+    let query_version =
+        |version: TransactionVersion| TransactionVersion(version.0 + QUERY_VERSION_BASE);
+    assert_eq!(prices.price_for_version(&query_version(TransactionVersion::TWO)), GasPrice(7));
+    assert_eq!(prices.price_for_version(&query_version(TransactionVersion::THREE)), GasPrice(5));
+}
+
+#[test]
+fn block_body_validate_and_iter() {
+    let chain_id = ChainId::Mainnet;
+    let Block { header, body } = block_with_commitments(&chain_id, &get_state_diff());
+    body.validate(header.block_number, None).unwrap();
+    body.validate(header.block_number, Some(&chain_id)).unwrap();
+    assert_eq!(
+        body.iter().collect::<Vec<_>>(),
+        vec![(&body.transactions[0], &body.transaction_outputs[0], &body.transaction_hashes[0])]
+    );
+
+    let mut mismatched = body.clone();
+    mismatched.transaction_hashes.push(TransactionHash(felt!("0x1234")));
+    assert_matches!(
+        mismatched.validate(header.block_number, None),
+        Err(StarknetApiError::BlockVerification(BlockVerificationError::BodyLengthMismatch {
+            n_transactions: 1,
+            n_outputs: 1,
+            n_hashes: 2,
+            ..
+        }))
+    );
+
+    let mut tampered = body;
+    tampered.transaction_hashes[0] = TransactionHash(felt!("0x1234"));
+    tampered.validate(header.block_number, None).unwrap();
+    assert_matches!(
+        tampered.validate(header.block_number, Some(&chain_id)),
+        Err(StarknetApiError::BlockVerification(
+            BlockVerificationError::TransactionHashMismatch { index: 0, .. }
+        ))
+    );
+}
+
+#[test]
+fn block_body_receipts() {
+    let chain_id = ChainId::Mainnet;
+    let Block { header, body } = block_with_commitments(&chain_id, &get_state_diff());
+    let receipts = body.receipts(header.block_hash, header.block_number);
+    assert_eq!(receipts.len(), 1);
+    assert_eq!(receipts[0].block_hash, header.block_hash);
+    assert_eq!(receipts[0].block_number, header.block_number);
+    assert_eq!(receipts[0].transaction_hash, body.transaction_hashes[0]);
+    assert_eq!(receipts[0].output, body.transaction_outputs[0]);
+
+    let receipt_elements =
+        receipts.iter().map(TransactionReceipt::for_commitment).collect::<Vec<_>>();
+    assert_eq!(
+        Some(calculate_receipt_commitment::<Poseidon>(&receipt_elements)),
+        header.receipt_commitment
+    );
+}
+
+#[test]
+fn gas_price_mul() {
+    let gas_price = GasPrice(1_000_000_000);
+    assert_eq!(gas_price.checked_mul(3), Some(Fee(3_000_000_000)));
+    assert_eq!(gas_price.saturating_mul(3), Fee(3_000_000_000));
+
+    let gas_price = GasPrice(u128::MAX / 2);
+    assert_eq!(gas_price.checked_mul(3), None);
+    assert_eq!(gas_price.saturating_mul(3), Fee(u128::MAX));
+}
+
+#[test]
+fn parse_block_number_and_gas_price() {
+    assert_eq!("635000".parse::<BlockNumber>().unwrap(), BlockNumber(635000));
+    assert_matches!("0x10".parse::<BlockNumber>(), Err(StarknetApiError::ParseIntError(_)));
+    assert_matches!("-1".parse::<BlockNumber>(), Err(StarknetApiError::ParseIntError(_)));
+
+    assert_eq!("1000".parse::<GasPrice>().unwrap(), GasPrice(1000));
+    assert_eq!("0x3e8".parse::<GasPrice>().unwrap(), GasPrice(1000));
+    assert_matches!("0xg".parse::<GasPrice>(), Err(StarknetApiError::ParseIntError(_)));
+}
+
+#[test]
+fn block_number_and_gas_price_from_ints() {
+    assert_eq!(BlockNumber::from(10_u64), BlockNumber(10));
+    assert_eq!(BlockNumber::from(10_u8), BlockNumber(10));
+    let block_number: BlockNumber = 10_u32.into();
+    assert_eq!(block_number, BlockNumber(10));
+
+    assert_eq!(GasPrice::from(u128::MAX), GasPrice(u128::MAX));
+    assert_eq!(GasPrice::from(u64::MAX), GasPrice(u64::MAX.into()));
+    let gas_price: GasPrice = 7_u16.into();
+    assert_eq!(gas_price, GasPrice(7));
+}
@@ -0,0 +1,236 @@
+//! [Borsh](https://borsh.io/) serialization of the Starknet types.
+//!
+//! Felts are serialized as 32 big-endian bytes. Deserialization rejects non-canonical felts, i.e.,
+//! values that are not smaller than the field prime, and felts out of the range of the
+//! deserialized type.
+#[cfg(test)]
+#[path = "borsh_codec_test.rs"]
+mod borsh_codec_test;
+
+use std::hash::Hash;
+use std::io::{self, Read, Write};
+use std::sync::Arc;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use indexmap::IndexMap;
+use primitive_types::H160;
+use starknet_types_core::felt::Felt;
+
+use crate::block::BlockHash;
+use crate::core::{
+    ClassHash, CompiledClassHash, ContractAddress, EntryPointSelector, EthAddress, EventCommitment,
+    GlobalRoot, Nonce, PatriciaKey, ReceiptCommitment, TransactionCommitment,
+};
+use crate::deprecated_contract_class::ContractClass as DeprecatedContractClass;
+use crate::hash::PoseidonHash;
+use crate::state::StorageKey;
+use crate::transaction::{
+    AccountDeploymentData, Calldata, ContractAddressSalt, EventData, EventKey, L1ToL2Payload,
+    L2ToL1Payload, PaymasterData, TransactionHash, TransactionSignature, TransactionVersion,
+};
+use crate::StarknetApiError;
+
+const FELT_SIZE: usize = 32;
+
+fn serialize_felt<W: Write>(felt: &Felt, writer: &mut W) -> io::Result<()> {
+    writer.write_all(&felt.to_bytes_be())
+}
+
+fn deserialize_felt<R: Read>(reader: &mut R) -> io::Result<Felt> {
+    let bytes = <[u8; FELT_SIZE]>::deserialize_reader(reader)?;
+    let felt = Felt::from_bytes_be(&bytes);
+    if felt.to_bytes_be() != bytes {
+        return Err(invalid_data("Felt is not smaller than the field prime."));
+    }
+    Ok(felt)
+}
+
+/// Serializes a length-prefixed sequence of felts.
+pub(crate) fn serialize_felts<W: Write>(felts: &[Felt], writer: &mut W) -> io::Result<()> {
+    u32::try_from(felts.len()).map_err(|_| invalid_data("Too many felts."))?.serialize(writer)?;
+    felts.iter().try_for_each(|felt| serialize_felt(felt, writer))
+}
+
+/// Deserializes a sequence of felts serialized by [serialize_felts].
+pub(crate) fn deserialize_felts<R: Read>(reader: &mut R) -> io::Result<Vec<Felt>> {
+    let len = u32::deserialize_reader(reader)?;
+    (0..len).map(|_| deserialize_felt(reader)).collect()
+}
+
+fn invalid_data<E>(error: E) -> io::Error
+where
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+macro_rules! impl_borsh_for_felt_newtype {
+    ($type:ident, $to_felt:expr, $from_felt:expr) => {
+        impl BorshSerialize for $type {
+            fn serialize<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+                let to_felt: fn(&$type) -> Felt = $to_felt;
+                serialize_felt(&to_felt(self), writer)
+            }
+        }
+
+        impl BorshDeserialize for $type {
+            fn deserialize_reader<R: Read>(reader: &mut R) -> io::Result<Self> {
+                let from_felt: fn(Felt) -> Result<$type, StarknetApiError> = $from_felt;
+                from_felt(deserialize_felt(reader)?).map_err(invalid_data)
+            }
+        }
+    };
+    ($($type:ident),+ $(,)?) => {
+        $(impl_borsh_for_felt_newtype!($type, |value| value.0, |felt| Ok($type(felt)));)+
+    };
+}
+
+macro_rules! impl_borsh_for_felt_vector_newtype {
+    ($type:ident, $to_felts:expr, $from_felts:expr) => {
+        impl BorshSerialize for $type {
+            fn serialize<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+                let to_felts: fn(&$type) -> &[Felt] = $to_felts;
+                serialize_felts(to_felts(self), writer)
+            }
+        }
+
+        impl BorshDeserialize for $type {
+            fn deserialize_reader<R: Read>(reader: &mut R) -> io::Result<Self> {
+                let from_felts: fn(Vec<Felt>) -> $type = $from_felts;
+                Ok(from_felts(deserialize_felts(reader)?))
+            }
+        }
+    };
+    ($($type:ident),+ $(,)?) => {
+        $(impl_borsh_for_felt_vector_newtype!($type, |value| &value.0, $type);)+
+    };
+}
+
+impl_borsh_for_felt_newtype!(
+    BlockHash,
+    ClassHash,
+    CompiledClassHash,
+    ContractAddressSalt,
+    EntryPointSelector,
+    EventCommitment,
+    EventKey,
+    GlobalRoot,
+    Nonce,
+    PoseidonHash,
+    ReceiptCommitment,
+    TransactionCommitment,
+    TransactionHash,
+    TransactionVersion,
+);
+impl_borsh_for_felt_newtype!(PatriciaKey, |key| *key.key(), PatriciaKey::try_from);
+impl_borsh_for_felt_newtype!(
+    ContractAddress,
+    |address| *address.0.key(),
+    ContractAddress::try_from
+);
+impl_borsh_for_felt_newtype!(
+    StorageKey,
+    |key| *key.0.key(),
+    |felt| Ok(StorageKey(PatriciaKey::try_from(felt)?))
+);
+
+impl_borsh_for_felt_vector_newtype!(
+    AccountDeploymentData,
+    EventData,
+    L1ToL2Payload,
+    L2ToL1Payload,
+    PaymasterData,
+    TransactionSignature,
+);
+impl_borsh_for_felt_vector_newtype!(
+    Calldata,
+    |calldata| calldata.0.as_slice(),
+    |felts| Calldata(Arc::new(felts))
+);
+
+impl BorshSerialize for EthAddress {
+    fn serialize<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(self.0.as_bytes())
+    }
+}
+
+impl BorshDeserialize for EthAddress {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> io::Result<Self> {
+        Ok(EthAddress(H160(<[u8; 20]>::deserialize_reader(reader)?)))
+    }
+}
+
+// The program of a deprecated contract class is an arbitrary JSON, so the class is serialized as
+// its length-prefixed JSON encoding.
+impl BorshSerialize for DeprecatedContractClass {
+    fn serialize<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        serde_json::to_vec(self).map_err(invalid_data)?.serialize(writer)
+    }
+}
+
+impl BorshDeserialize for DeprecatedContractClass {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> io::Result<Self> {
+        serde_json::from_slice(&Vec::<u8>::deserialize_reader(reader)?).map_err(invalid_data)
+    }
+}
+
+/// Serializes an [IndexMap] as a length-prefixed sequence of key-value pairs, preserving its order.
+pub(crate) fn serialize_index_map<K, V, W>(map: &IndexMap<K, V>, writer: &mut W) -> io::Result<()>
+where
+    K: BorshSerialize,
+    V: BorshSerialize,
+    W: Write,
+{
+    u32::try_from(map.len()).map_err(|_| invalid_data("Too many map entries."))?.serialize(writer)?;
+    map.iter().try_for_each(|entry| entry.serialize(writer))
+}
+
+/// Deserializes an [IndexMap] serialized by [serialize_index_map].
+pub(crate) fn deserialize_index_map<K, V, R>(reader: &mut R) -> io::Result<IndexMap<K, V>>
+where
+    K: BorshDeserialize + Eq + Hash,
+    V: BorshDeserialize,
+    R: Read,
+{
+    let len = u32::deserialize_reader(reader)?;
+    (0..len).map(|_| <(K, V)>::deserialize_reader(reader)).collect()
+}
+
+/// Serializes the storage diffs of a [StateDiff](`crate::state::StateDiff`) or a
+/// [ThinStateDiff](`crate::state::ThinStateDiff`).
+pub(crate) fn serialize_storage_diffs<W: Write>(
+    storage_diffs: &IndexMap<ContractAddress, IndexMap<StorageKey, Felt>>,
+    writer: &mut W,
+) -> io::Result<()> {
+    u32::try_from(storage_diffs.len())
+        .map_err(|_| invalid_data("Too many storage diffs."))?
+        .serialize(writer)?;
+    for (address, storage_entries) in storage_diffs {
+        address.serialize(writer)?;
+        u32::try_from(storage_entries.len())
+            .map_err(|_| invalid_data("Too many storage entries."))?
+            .serialize(writer)?;
+        for (key, value) in storage_entries {
+            key.serialize(writer)?;
+            serialize_felt(value, writer)?;
+        }
+    }
+    Ok(())
+}
+
+/// Deserializes the storage diffs serialized by [serialize_storage_diffs].
+pub(crate) fn deserialize_storage_diffs<R: Read>(
+    reader: &mut R,
+) -> io::Result<IndexMap<ContractAddress, IndexMap<StorageKey, Felt>>> {
+    let n_contracts = u32::deserialize_reader(reader)?;
+    (0..n_contracts)
+        .map(|_| {
+            let address = ContractAddress::deserialize_reader(reader)?;
+            let n_entries = u32::deserialize_reader(reader)?;
+            let storage_entries = (0..n_entries)
+                .map(|_| Ok((StorageKey::deserialize_reader(reader)?, deserialize_felt(reader)?)))
+                .collect::<io::Result<IndexMap<_, _>>>()?;
+            Ok((address, storage_entries))
+        })
+        .collect()
+}
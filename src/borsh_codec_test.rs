@@ -0,0 +1,191 @@
+use std::collections::BTreeMap;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use indexmap::indexmap;
+use starknet_types_core::felt::Felt;
+
+use crate::block::{
+    Block, BlockBody, BlockHash, BlockHeader, BlockNumber, BlockTimestamp, GasPrice,
+    GasPricePerToken, StarknetVersion,
+};
+use crate::core::{
+    ClassHash, CompiledClassHash, ContractAddress, EntryPointSelector, EventCommitment, GlobalRoot,
+    Nonce, PatriciaKey, ReceiptCommitment, SequencerContractAddress, StateDiffCommitment,
+    TransactionCommitment,
+};
+use crate::data_availability::{DataAvailabilityMode, L1DataAvailabilityMode};
+use crate::deprecated_contract_class::ContractClass as DeprecatedContractClass;
+use crate::hash::PoseidonHash;
+use crate::state::{
+    ContractClass, EntryPoint, EntryPointType, EntryPointsByType, FunctionIndex, StateDiff,
+    StorageKey, ThinStateDiff,
+};
+use crate::transaction::{
+    AccountDeploymentData, Calldata, Event, EventContent, EventData, EventKey, Fee,
+    InvokeTransaction, InvokeTransactionOutput, InvokeTransactionV3, PaymasterData, Resource,
+    ResourceAmount, ResourceBounds, ResourceBoundsMapping, ResourcePrice, Tip, Transaction,
+    TransactionHash, TransactionOutput, TransactionSignature,
+};
+use crate::{calldata, class_hash, contract_address, felt, patricia_key};
+
+fn round_trip<T>(value: &T) -> T
+where
+    T: BorshSerialize + BorshDeserialize,
+{
+    let bytes = borsh::to_vec(value).unwrap();
+    T::try_from_slice(&bytes).unwrap()
+}
+
+#[test]
+fn felt_newtype_is_serialized_as_32_bytes() {
+    let class_hash = class_hash!("0x1234");
+    let bytes = borsh::to_vec(&class_hash).unwrap();
+    assert_eq!(bytes, Felt::from(0x1234_u16).to_bytes_be());
+    assert_eq!(round_trip(&class_hash), class_hash);
+}
+
+#[test]
+fn non_canonical_felt_is_rejected() {
+    assert!(ClassHash::try_from_slice(&[0xff; 32]).is_err());
+}
+
+#[test]
+fn out_of_range_contract_address_is_rejected() {
+    let bytes = borsh::to_vec(&ClassHash(Felt::MAX)).unwrap();
+    assert!(ContractAddress::try_from_slice(&bytes).is_err());
+}
+
+fn invoke_v3_transaction() -> Transaction {
+    Transaction::Invoke(InvokeTransaction::V3(InvokeTransactionV3 {
+        resource_bounds: ResourceBoundsMapping(BTreeMap::from([
+            (
+                Resource::L1Gas,
+                ResourceBounds {
+                    max_amount: ResourceAmount(0x186a0),
+                    max_price_per_unit: ResourcePrice(0x5af3107a4000),
+                },
+            ),
+            (Resource::L2Gas, ResourceBounds::default()),
+        ])),
+        tip: Tip(1),
+        signature: TransactionSignature(vec![felt!("0x1"), felt!("0x2")]),
+        nonce: Nonce(felt!("0x5")),
+        sender_address: contract_address!("0x123"),
+        calldata: calldata![felt!("0x1"), felt!("0x2"), felt!("0x3")],
+        nonce_data_availability_mode: DataAvailabilityMode::L1,
+        fee_data_availability_mode: DataAvailabilityMode::L2,
+        paymaster_data: PaymasterData(vec![]),
+        account_deployment_data: AccountDeploymentData(vec![felt!("0x7")]),
+    }))
+}
+
+#[test]
+fn invoke_v3_transaction_round_trip() {
+    let transaction = invoke_v3_transaction();
+    assert_eq!(round_trip(&transaction), transaction);
+}
+
+#[test]
+fn thin_state_diff_round_trip() {
+    let state_diff = ThinStateDiff {
+        deployed_contracts: indexmap! {
+            contract_address!("0x2") => class_hash!("0x20"),
+            contract_address!("0x1") => class_hash!("0x10"),
+        },
+        storage_diffs: indexmap! {
+            contract_address!("0x1") => indexmap! {
+                StorageKey(patricia_key!("0x5")) => felt!("0x55"),
+                StorageKey(patricia_key!("0x4")) => felt!("0x44"),
+            },
+        },
+        declared_classes: indexmap! {
+            class_hash!("0x30") => CompiledClassHash(felt!("0x31")),
+        },
+        deprecated_declared_classes: vec![class_hash!("0x40")],
+        nonces: indexmap! { contract_address!("0x1") => Nonce(felt!("0x1")) },
+        replaced_classes: indexmap! { contract_address!("0x2") => class_hash!("0x21") },
+    };
+    let deserialized = round_trip(&state_diff);
+    assert_eq!(deserialized, state_diff);
+    // The order of the maps is preserved.
+    assert!(deserialized.deployed_contracts.keys().eq(state_diff.deployed_contracts.keys()));
+    assert!(deserialized.storage_diffs[0].keys().eq(state_diff.storage_diffs[0].keys()));
+}
+
+#[test]
+fn block_round_trip() {
+    let header = BlockHeader {
+        block_hash: BlockHash(felt!("0x1")),
+        parent_hash: BlockHash(felt!("0x2")),
+        block_number: BlockNumber(3),
+        l1_gas_price: GasPricePerToken { price_in_fri: GasPrice(4), price_in_wei: GasPrice(5) },
+        l1_data_gas_price: GasPricePerToken {
+            price_in_fri: GasPrice(6),
+            price_in_wei: GasPrice(7),
+        },
+        state_root: GlobalRoot(felt!("0x8")),
+        sequencer: SequencerContractAddress(contract_address!("0x9")),
+        timestamp: BlockTimestamp(10),
+        l1_da_mode: L1DataAvailabilityMode::Blob,
+        state_diff_commitment: Some(StateDiffCommitment(PoseidonHash(felt!("0xb")))),
+        state_diff_length: Some(12),
+        transaction_commitment: Some(TransactionCommitment(felt!("0xd"))),
+        event_commitment: Some(EventCommitment(felt!("0xe"))),
+        n_transactions: 1,
+        n_events: 1,
+        receipt_commitment: Some(ReceiptCommitment(felt!("0x11"))),
+        starknet_version: StarknetVersion("0.13.1".to_owned()),
+    };
+    let output = TransactionOutput::Invoke(InvokeTransactionOutput {
+        actual_fee: Fee(100),
+        events: vec![Event {
+            from_address: contract_address!("0x123"),
+            content: EventContent {
+                keys: vec![EventKey(felt!("0x1"))],
+                data: EventData(vec![felt!("0x2")]),
+            },
+        }],
+        ..Default::default()
+    });
+    let block = Block {
+        header,
+        body: BlockBody {
+            transactions: vec![invoke_v3_transaction()],
+            transaction_outputs: vec![output],
+            transaction_hashes: vec![TransactionHash(felt!("0xf"))],
+        },
+    };
+    assert_eq!(round_trip(&block), block);
+}
+
+#[test]
+fn state_diff_round_trip() {
+    let contract_class = ContractClass {
+        sierra_program: vec![felt!("0x1"), felt!("0x2")],
+        entry_points_by_type: EntryPointsByType(BTreeMap::from([(
+            EntryPointType::External,
+            vec![EntryPoint {
+                function_idx: FunctionIndex(1),
+                selector: EntryPointSelector(felt!("0x3")),
+            }],
+        )])),
+        abi: "[]".to_owned(),
+    };
+    let state_diff = StateDiff {
+        deployed_contracts: indexmap! { contract_address!("0x1") => class_hash!("0x10") },
+        storage_diffs: indexmap! {
+            contract_address!("0x1") => indexmap! {
+                StorageKey(patricia_key!("0x5")) => felt!("0x55"),
+            },
+        },
+        declared_classes: indexmap! {
+            class_hash!("0x30") => (CompiledClassHash(felt!("0x31")), contract_class),
+        },
+        deprecated_declared_classes: indexmap! {
+            class_hash!("0x40") => DeprecatedContractClass::default(),
+        },
+        nonces: indexmap! { contract_address!("0x1") => Nonce(felt!("0x1")) },
+        replaced_classes: indexmap! { contract_address!("0x2") => class_hash!("0x21") },
+    };
+    assert_eq!(round_trip(&state_diff), state_diff);
+}
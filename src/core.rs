@@ -3,8 +3,10 @@
 mod core_test;
 
 use core::fmt::Display;
+use std::collections::HashMap;
 use std::fmt::Debug;
 
+use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
 use derive_more::Display;
 use once_cell::sync::Lazy;
 use primitive_types::H160;
@@ -14,9 +16,14 @@ use starknet_types_core::hash::{Pedersen, StarkHash as CoreStarkHash};
 
 use crate::crypto::utils::PublicKey;
 use crate::hash::{PoseidonHash, StarkHash};
-use crate::serde_utils::{BytesAsHex, PrefixedBytesAsHex};
+use crate::serde_utils::{
+    bytes_from_hex_str, BytesAsHex, InnerDeserializationError, PrefixedBytesAsHex,
+};
 use crate::transaction::{Calldata, ContractAddressSalt};
-use crate::{impl_from_through_intermediate, StarknetApiError};
+use crate::{
+    impl_from_hex_str, impl_from_through_intermediate, impl_hex_display, impl_inner_conversions,
+    StarknetApiError,
+};
 
 /// A chain id.
 #[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord)]
@@ -70,6 +77,69 @@ impl ChainId {
     pub fn as_hex(&self) -> String {
         format!("0x{}", hex::encode(self.to_string()))
     }
+
+    /// Parses a chain id from its ASCII encoding as a `0x`-prefixed hex string, i.e., the inverse
+    /// of [`ChainId::as_hex`]. Leading zeros, as in the felt form of the chain id, are ignored.
+    pub fn from_hex(hex_str: &str) -> Result<Self, StarknetApiError> {
+        let digits = hex_str
+            .strip_prefix("0x")
+            .ok_or(InnerDeserializationError::MissingPrefix { hex_str: hex_str.into() })?;
+        let padded_digits =
+            if digits.len() % 2 == 0 { digits.to_owned() } else { format!("0{digits}") };
+        let bytes = hex::decode(padded_digits).map_err(InnerDeserializationError::from)?;
+        let first_non_zero = bytes.iter().position(|byte| *byte != 0).unwrap_or(bytes.len());
+        let chain_id = String::from_utf8(bytes[first_non_zero..].to_vec())
+            .map_err(|_| StarknetApiError::InvalidChainId(hex_str.to_owned()))?;
+        Ok(ChainId::from(chain_id))
+    }
+
+    /// Returns the chain id as a felt, i.e., its name encoded as a
+    /// [shortstring](`encode_shortstring`).
+    pub fn as_felt(&self) -> Result<Felt, StarknetApiError> {
+        encode_shortstring(&self.to_string())
+    }
+
+    /// Decodes a chain id from its felt form, i.e., the inverse of [`ChainId::as_felt`]. Known
+    /// chain names are mapped to their variants.
+    ///
+    /// A felt that isn't a valid shortstring is kept as the hex string of the felt in
+    /// [`ChainId::Other`], so that no information is lost.
+    pub fn from_felt(felt: Felt) -> Self {
+        match decode_shortstring(felt) {
+            Ok(name) => ChainId::from(name),
+            Err(_) => ChainId::Other(format!("{felt:#x}")),
+        }
+    }
+}
+
+/// The maximal length of a shortstring, i.e., an ASCII string encoded as a felt.
+pub const MAX_SHORTSTRING_LENGTH: usize = 31;
+
+/// Encodes an ASCII string of at most [MAX_SHORTSTRING_LENGTH] characters as a felt, whose big
+/// endian bytes are the characters of the string (a Cairo shortstring).
+pub fn encode_shortstring(ascii_str: &str) -> Result<Felt, StarknetApiError> {
+    if !ascii_str.is_ascii() {
+        return Err(StarknetApiError::NonAsciiString(ascii_str.to_owned()));
+    }
+    if ascii_str.len() > MAX_SHORTSTRING_LENGTH {
+        return Err(StarknetApiError::OutOfRange { string: ascii_str.to_owned() });
+    }
+    Ok(Felt::from_bytes_be_slice(ascii_str.as_bytes()))
+}
+
+/// Decodes a felt as a shortstring, i.e., the inverse of [encode_shortstring]. Leading zero bytes
+/// are ignored.
+pub fn decode_shortstring(felt: Felt) -> Result<String, StarknetApiError> {
+    let bytes = felt.to_bytes_be();
+    let first_non_zero = bytes.iter().position(|byte| *byte != 0).unwrap_or(bytes.len());
+    let ascii_bytes = &bytes[first_non_zero..];
+    if ascii_bytes.len() > MAX_SHORTSTRING_LENGTH {
+        return Err(StarknetApiError::OutOfRange { string: format!("{felt:#x}") });
+    }
+    if !ascii_bytes.is_ascii() {
+        return Err(StarknetApiError::NonAsciiString(format!("{felt:#x}")));
+    }
+    Ok(String::from_utf8(ascii_bytes.to_vec()).expect("ASCII bytes are valid UTF-8"))
 }
 
 /// The address of a contract, used for example in [StateDiff](`crate::state::StateDiff`),
@@ -79,6 +149,40 @@ impl ChainId {
 // The block hash table is stored in address 0x1,
 // this is a special address that is not used for contracts.
 pub const BLOCK_HASH_TABLE_ADDRESS: ContractAddress = ContractAddress(PatriciaKey(StarkHash::ONE));
+
+/// Addresses of contracts with a fixed role in Starknet.
+pub mod well_known_addresses {
+    use starknet_types_core::felt::Felt;
+
+    use super::{ContractAddress, PatriciaKey, BLOCK_HASH_TABLE_ADDRESS};
+
+    const fn address(hex_str: &str) -> ContractAddress {
+        ContractAddress::from_hex_unchecked(hex_str)
+    }
+
+    /// The zero address, e.g., the deployer address of deploy account transactions.
+    pub const ZERO_ADDRESS: ContractAddress = ContractAddress(PatriciaKey(Felt::ZERO));
+
+    /// The system contract that stores the hashes of past blocks.
+    pub const BLOCK_HASH_CONTRACT_ADDRESS: ContractAddress = BLOCK_HASH_TABLE_ADDRESS;
+
+    /// The ETH fee token, for transactions up to V2. Identical on Mainnet and Sepolia.
+    pub const ETH_FEE_TOKEN_ADDRESS: ContractAddress =
+        address("0x049d36570d4e46f48e99674bd3fcc84644ddd6b96f7c741b1562b82f9e004dc7");
+    pub const MAINNET_ETH_FEE_TOKEN_ADDRESS: ContractAddress = ETH_FEE_TOKEN_ADDRESS;
+    pub const SEPOLIA_ETH_FEE_TOKEN_ADDRESS: ContractAddress = ETH_FEE_TOKEN_ADDRESS;
+
+    /// The STRK fee token, for transactions from V3 on. Identical on Mainnet and Sepolia.
+    pub const STRK_FEE_TOKEN_ADDRESS: ContractAddress =
+        address("0x04718f5a0fc34cc1af16a1cdee98ffb20c31f5cd61d6ab07201858f4287c938d");
+    pub const MAINNET_STRK_FEE_TOKEN_ADDRESS: ContractAddress = STRK_FEE_TOKEN_ADDRESS;
+    pub const SEPOLIA_STRK_FEE_TOKEN_ADDRESS: ContractAddress = STRK_FEE_TOKEN_ADDRESS;
+
+    /// The address of the Mainnet sequencer, which receives the fees of the transactions.
+    pub const MAINNET_SEQUENCER_ADDRESS: ContractAddress =
+        address("0x01176a1bd84444c89232ec27754698e5d2e7e1a7f1539f12027f28b23ec9f3d8");
+}
+
 #[derive(
     Debug,
     Default,
@@ -96,6 +200,16 @@ pub const BLOCK_HASH_TABLE_ADDRESS: ContractAddress = ContractAddress(PatriciaKe
 )]
 pub struct ContractAddress(pub PatriciaKey);
 
+impl_inner_conversions!(ContractAddress: PatriciaKey);
+
+impl ContractAddress {
+    /// Creates an address from a hex string, in a const context. See
+    /// [PatriciaKey::from_hex_unchecked] for the caveats.
+    pub const fn from_hex_unchecked(hex_str: &str) -> Self {
+        Self(PatriciaKey::from_hex_unchecked(hex_str))
+    }
+}
+
 impl From<ContractAddress> for Felt {
     fn from(contract_address: ContractAddress) -> Felt {
         **contract_address
@@ -121,6 +235,8 @@ pub static L2_ADDRESS_UPPER_BOUND: Lazy<NonZeroFelt> = Lazy::new(|| {
     NonZeroFelt::try_from(CONTRACT_ADDRESS_DOMAIN_SIZE - Felt::from(MAX_STORAGE_ITEM_SIZE)).unwrap()
 });
 
+impl_from_hex_str!(ContractAddress: ContractAddress::try_from);
+
 impl TryFrom<StarkHash> for ContractAddress {
     type Error = StarknetApiError;
     fn try_from(hash: StarkHash) -> Result<Self, Self::Error> {
@@ -136,6 +252,20 @@ pub fn calculate_contract_address(
     deployer_address: ContractAddress,
 ) -> Result<ContractAddress, StarknetApiError> {
     let constructor_calldata_hash = Pedersen::hash_array(&constructor_calldata.0);
+    calculate_contract_address_from_calldata_hash(
+        salt,
+        class_hash,
+        constructor_calldata_hash,
+        deployer_address,
+    )
+}
+
+fn calculate_contract_address_from_calldata_hash(
+    salt: ContractAddressSalt,
+    class_hash: ClassHash,
+    constructor_calldata_hash: StarkHash,
+    deployer_address: ContractAddress,
+) -> Result<ContractAddress, StarknetApiError> {
     let contract_address_prefix = format!("0x{}", hex::encode(CONTRACT_ADDRESS_PREFIX));
     let address = Pedersen::hash_array(&[
         Felt::from_hex(contract_address_prefix.as_str()).map_err(|_| {
@@ -151,6 +281,44 @@ pub fn calculate_contract_address(
     ContractAddress::try_from(address)
 }
 
+/// A cache of counterfactual account addresses, i.e., the addresses of contracts deployed by a
+/// [DeployAccountTransaction](`crate::transaction::DeployAccountTransaction`), keyed by the class
+/// hash, the salt and the hash of the constructor calldata.
+#[derive(Clone, Debug, Default)]
+pub struct DeployAccountAddress {
+    addresses: HashMap<(ClassHash, ContractAddressSalt, StarkHash), ContractAddress>,
+}
+
+impl DeployAccountAddress {
+    /// Returns the address of an account deployed with the given arguments, computing it only if
+    /// it isn't cached.
+    pub fn get_or_compute(
+        &mut self,
+        class_hash: ClassHash,
+        salt: ContractAddressSalt,
+        constructor_calldata: &Calldata,
+    ) -> Result<ContractAddress, StarknetApiError> {
+        let constructor_calldata_hash = Pedersen::hash_array(&constructor_calldata.0);
+        let key = (class_hash, salt, constructor_calldata_hash);
+        if let Some(address) = self.addresses.get(&key) {
+            return Ok(*address);
+        }
+        let address = calculate_contract_address_from_calldata_hash(
+            salt,
+            class_hash,
+            constructor_calldata_hash,
+            ContractAddress::default(),
+        )?;
+        self.addresses.insert(key, address);
+        Ok(address)
+    }
+}
+
+/// Calculates the hash of a compiled (CASM) contract class.
+pub fn compiled_class_hash(casm: &CasmContractClass) -> CompiledClassHash {
+    CompiledClassHash(casm.compiled_class_hash())
+}
+
 /// The hash of a ContractClass.
 #[derive(
     Debug,
@@ -167,8 +335,20 @@ pub fn calculate_contract_address(
     Display,
     derive_more::Deref,
 )]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ClassHash(pub StarkHash);
 
+impl_inner_conversions!(ClassHash: StarkHash);
+impl_from_hex_str!(ClassHash: |felt| Ok(ClassHash(felt)));
+
+impl ClassHash {
+    /// Creates a class hash from a hex string, in a const context. Like
+    /// [Felt::from_hex_unchecked], panics if the string is not a valid hex felt.
+    pub const fn from_hex_unchecked(hex_str: &str) -> Self {
+        Self(Felt::from_hex_unchecked(hex_str))
+    }
+}
+
 /// The hash of a compiled ContractClass.
 #[derive(
     Debug,
@@ -184,6 +364,7 @@ pub struct ClassHash(pub StarkHash);
     Ord,
     Display,
 )]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct CompiledClassHash(pub StarkHash);
 
 /// A general type for nonces.
@@ -201,8 +382,12 @@ pub struct CompiledClassHash(pub StarkHash);
     Ord,
     derive_more::Deref,
 )]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Nonce(pub Felt);
 
+impl_inner_conversions!(Nonce: Felt);
+impl_hex_display!(Nonce);
+
 impl Nonce {
     pub fn try_increment(&self) -> Result<Self, StarknetApiError> {
         // Check if an overflow occurred during increment.
@@ -212,14 +397,37 @@ impl Nonce {
         }
         Ok(Self(incremented))
     }
+
+    /// Advances the nonce by `n`, e.g., past a batch of transactions. Fails, like
+    /// [Nonce::try_increment], if the nonce would wrap around the field prime.
+    pub fn try_increment_by(&self, n: u64) -> Result<Self, StarknetApiError> {
+        // The sum wraps around iff it's smaller than the original nonce.
+        let incremented = self.0 + Felt::from(n);
+        if incremented < self.0 {
+            return Err(StarknetApiError::OutOfRange { string: format!("{:?} + {n}", self) });
+        }
+        Ok(Self(incremented))
+    }
+
+    /// Returns the previous nonce, e.g., to roll back a rejected transaction. Fails if the nonce
+    /// is zero.
+    pub fn try_decrement(&self) -> Result<Self, StarknetApiError> {
+        if self.0 == Felt::ZERO {
+            return Err(StarknetApiError::OutOfRange { string: format!("{:?} - 1", self) });
+        }
+        Ok(Self(self.0 - Felt::ONE))
+    }
 }
 
 /// The selector of an [EntryPoint](`crate::deprecated_contract_class::EntryPoint`).
 #[derive(
     Debug, Copy, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord,
 )]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct EntryPointSelector(pub StarkHash);
 
+impl_hex_display!(EntryPointSelector);
+
 /// The root of the global state at a [Block](`crate::block::Block`)
 /// and [StateUpdate](`crate::state::StateUpdate`).
 #[derive(
@@ -290,8 +498,16 @@ pub struct EventCommitment(pub StarkHash);
 pub struct ReceiptCommitment(pub StarkHash);
 
 #[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct StateDiffCommitment(pub PoseidonHash);
 
+impl Display for StateDiffCommitment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
 /// A key for nodes of a Patricia tree.
 // Invariant: key is in range.
 #[derive(
@@ -315,12 +531,35 @@ pub struct PatriciaKey(StarkHash);
 pub const PATRICIA_KEY_UPPER_BOUND: &str =
     "0x800000000000000000000000000000000000000000000000000000000000000";
 
+/// Parses a `0x`-prefixed hex string of up to 32 bytes into a felt. Values that are not smaller
+/// than the field prime are rejected rather than reduced.
+pub fn felt_from_prefixed_hex(hex_str: &str) -> Result<Felt, StarknetApiError> {
+    let bytes = bytes_from_hex_str::<32, true>(hex_str)?;
+    let felt = Felt::from_bytes_be(&bytes);
+    if felt.to_bytes_be() != bytes {
+        return Err(StarknetApiError::OutOfRange { string: format!("{hex_str} is not a felt") });
+    }
+    Ok(felt)
+}
+
 impl PatriciaKey {
     pub fn key(&self) -> &StarkHash {
         &self.0
     }
+
+    /// Creates a key from a hex string, in a const context. Like [Felt::from_hex_unchecked],
+    /// panics if the string is not a valid hex felt.
+    ///
+    /// Unlike [PatriciaKey::try_from], the key is not checked to be smaller than
+    /// [PATRICIA_KEY_UPPER_BOUND]; it is up to the caller to pass a key in range. Use
+    /// [str::parse] for unchecked input.
+    pub const fn from_hex_unchecked(hex_str: &str) -> Self {
+        Self(Felt::from_hex_unchecked(hex_str))
+    }
 }
 
+impl_inner_conversions!(PatriciaKey: StarkHash);
+
 impl From<u128> for PatriciaKey {
     fn from(val: u128) -> Self {
         PatriciaKey::try_from(Felt::from(val)).expect("Failed to convert u128 to PatriciaKey.")
@@ -419,9 +658,27 @@ impl From<EthAddress> for PrefixedBytesAsHex<20_usize> {
 #[derive(
     Debug, Copy, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord,
 )]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct SequencerPublicKey(pub PublicKey);
 
 #[derive(
     Debug, Default, Clone, Copy, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord,
 )]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct SequencerContractAddress(pub ContractAddress);
+
+impl_inner_conversions!(SequencerContractAddress: ContractAddress);
+
+impl SequencerContractAddress {
+    /// Returns the contract address of the sequencer.
+    pub fn as_contract_address(&self) -> &ContractAddress {
+        &self.0
+    }
+}
+
+impl From<ContractAddress> for SequencerContractAddress {
+    fn from(contract_address: ContractAddress) -> Self {
+        SequencerContractAddress(contract_address)
+    }
+}
@@ -1,14 +1,18 @@
 use assert_matches::assert_matches;
+use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
 use starknet_types_core::felt::Felt;
 use starknet_types_core::hash::{Pedersen, StarkHash as CoreStarkHash};
 
 use crate::core::{
-    calculate_contract_address, ClassHash, ContractAddress, EthAddress, Nonce, PatriciaKey,
-    StarknetApiError, CONTRACT_ADDRESS_PREFIX, L2_ADDRESS_UPPER_BOUND,
+    calculate_contract_address, compiled_class_hash, decode_shortstring, encode_shortstring,
+    ChainId, ClassHash, CompiledClassHash, ContractAddress, DeployAccountAddress,
+    EntryPointSelector, EthAddress, Nonce, PatriciaKey, SequencerContractAddress, StarknetApiError,
+    CONTRACT_ADDRESS_PREFIX, L2_ADDRESS_UPPER_BOUND, PATRICIA_KEY_UPPER_BOUND,
 };
 use crate::hash::StarkHash;
-use crate::transaction::{Calldata, ContractAddressSalt};
-use crate::{class_hash, felt, patricia_key};
+use crate::serde_utils::InnerDeserializationError;
+use crate::transaction::{Calldata, ContractAddressSalt, TransactionHash};
+use crate::{class_hash, contract_address, felt, patricia_key};
 
 #[test]
 fn patricia_key_valid() {
@@ -82,6 +86,28 @@ fn nonce_overflow() {
     assert_matches!(overflowed_nonce, Err(StarknetApiError::OutOfRange { string: _err_str }));
 }
 
+#[test]
+fn nonce_increment_by() {
+    assert_eq!(Nonce(felt!("0x5")).try_increment_by(0).unwrap(), Nonce(felt!("0x5")));
+    assert_eq!(
+        Nonce(felt!("0x5")).try_increment_by(u64::MAX).unwrap(),
+        Nonce(Felt::from(u64::MAX) + Felt::from(5_u8))
+    );
+
+    // The boundary: reaching the max felt is fine, wrapping to zero or beyond isn't.
+    let nonce = Nonce(Felt::MAX - Felt::from(10_u8));
+    assert_eq!(nonce.try_increment_by(10).unwrap(), Nonce(Felt::MAX));
+    assert_matches!(nonce.try_increment_by(11), Err(StarknetApiError::OutOfRange { .. }));
+    assert_matches!(nonce.try_increment_by(u64::MAX), Err(StarknetApiError::OutOfRange { .. }));
+}
+
+#[test]
+fn nonce_decrement() {
+    assert_eq!(Nonce(felt!("0x5")).try_decrement().unwrap(), Nonce(felt!("0x4")));
+    assert_eq!(Nonce(Felt::ONE).try_decrement().unwrap(), Nonce(Felt::ZERO));
+    assert_matches!(Nonce(Felt::ZERO).try_decrement(), Err(StarknetApiError::OutOfRange { .. }));
+}
+
 #[test]
 fn test_patricia_key_display() {
     assert_eq!(format!("{}", patricia_key!(7_u8)), String::from("0x") + &"0".repeat(63) + "7");
@@ -94,3 +120,184 @@ fn test_contract_address_display() {
         String::from("0x") + &"0".repeat(62) + "10"
     );
 }
+
+#[test]
+fn compiled_class_hash_of_casm() {
+    let casm: CasmContractClass = serde_json::from_str(include_str!(
+        "../resources/account__account.compiled_contract_class.json"
+    ))
+    .unwrap();
+    // The expected hash is taken from the cairo-lang compiler's tests.
+    let expected_hash = felt!("0x4b552d087e9633fbecf2185d144fafca55e6581502c0fc93953c143757dc8bf");
+    assert_eq!(compiled_class_hash(&casm), CompiledClassHash(expected_hash));
+}
+
+#[test]
+fn inner_conversions() {
+    let felt = felt!("0x1234");
+    let patricia_key = patricia_key!("0x1234");
+
+    assert_eq!(patricia_key.into_inner(), felt);
+    assert_eq!(patricia_key.as_inner(), &felt);
+    assert_eq!(Felt::from(patricia_key), felt);
+
+    let contract_address = ContractAddress(patricia_key);
+    assert_eq!(contract_address.into_inner(), patricia_key);
+    assert_eq!(contract_address.as_inner(), &patricia_key);
+    assert_eq!(PatriciaKey::from(contract_address), patricia_key);
+    assert_eq!(Felt::from(contract_address), felt);
+
+    assert_eq!(ClassHash(felt).into_inner(), felt);
+    assert_eq!(ClassHash(felt).as_inner(), &felt);
+    assert_eq!(Felt::from(ClassHash(felt)), felt);
+
+    assert_eq!(Nonce(felt).into_inner(), felt);
+    assert_eq!(Nonce(felt).as_inner(), &felt);
+    assert_eq!(Felt::from(Nonce(felt)), felt);
+}
+
+#[test]
+fn sequencer_contract_address_conversions() {
+    let contract_address = contract_address!("0x1234");
+
+    let sequencer = SequencerContractAddress::from(contract_address);
+    assert_eq!(sequencer, SequencerContractAddress(contract_address));
+    assert_eq!(sequencer.as_contract_address(), &contract_address);
+    assert_eq!(ContractAddress::from(sequencer), contract_address);
+}
+
+#[test]
+fn chain_id_hex_round_trip() {
+    for chain_id in [ChainId::Sepolia, ChainId::Other("MY_CUSTOM_CHAIN".to_owned())] {
+        assert_eq!(ChainId::from_hex(&chain_id.as_hex()).unwrap(), chain_id);
+        // The felt form of the chain id, as used in signature domains, decodes the same.
+        let felt_hex = format!("{:#066x}", Felt::from_hex(&chain_id.as_hex()).unwrap());
+        assert_eq!(ChainId::from_hex(&felt_hex).unwrap(), chain_id);
+    }
+}
+
+#[test]
+fn chain_id_felt_round_trip() {
+    for chain_id in [ChainId::Mainnet, ChainId::Other("MY_CUSTOM_CHAIN".to_owned())] {
+        let felt = chain_id.as_felt().unwrap();
+        assert_eq!(felt, Felt::from_hex(&chain_id.as_hex()).unwrap());
+        assert_eq!(ChainId::from_felt(felt), chain_id);
+    }
+    assert_eq!(ChainId::from_felt(felt!("0x534e5f5345504f4c4941")), ChainId::Sepolia);
+
+    // A felt that isn't a shortstring is kept as hex.
+    assert_eq!(ChainId::from_felt(felt!("0xff41")), ChainId::Other("0xff41".to_owned()));
+}
+
+#[test]
+fn shortstring_round_trip() {
+    let encoded = encode_shortstring("SN_MAIN").unwrap();
+    assert_eq!(encoded, felt!("0x534e5f4d41494e"));
+    assert_eq!(decode_shortstring(encoded).unwrap(), "SN_MAIN");
+    assert_eq!(decode_shortstring(Felt::ZERO).unwrap(), "");
+
+    let max_length = "a".repeat(31);
+    assert_eq!(decode_shortstring(encode_shortstring(&max_length).unwrap()).unwrap(), max_length);
+    let too_long = "a".repeat(32);
+    assert_matches!(
+        encode_shortstring(&too_long),
+        Err(StarknetApiError::OutOfRange { string }) if string == too_long
+    );
+    assert_matches!(encode_shortstring("חלון"), Err(StarknetApiError::NonAsciiString(_)));
+    assert_matches!(decode_shortstring(felt!("0xff41")), Err(StarknetApiError::NonAsciiString(_)));
+    assert_matches!(decode_shortstring(Felt::MAX), Err(StarknetApiError::OutOfRange { .. }));
+}
+
+#[test]
+fn deploy_account_address_cache() {
+    let class_hash = class_hash!("0x110");
+    let salt = ContractAddressSalt(felt!("0x1337"));
+    let calldata = Calldata(vec![felt!("0x60"), felt!("0x70")].into());
+    let expected =
+        calculate_contract_address(salt, class_hash, &calldata, ContractAddress::default())
+            .unwrap();
+
+    let mut cache = DeployAccountAddress::default();
+    assert_eq!(cache.get_or_compute(class_hash, salt, &calldata).unwrap(), expected);
+    assert_eq!(cache.get_or_compute(class_hash, salt, &calldata).unwrap(), expected);
+    assert_eq!(cache.addresses.len(), 1);
+
+    // Changing any of the inputs results in a different address.
+    let other_calldata = Calldata(vec![felt!("0x60")].into());
+    let other_addresses = [
+        cache.get_or_compute(class_hash!("0x111"), salt, &calldata).unwrap(),
+        cache.get_or_compute(class_hash, ContractAddressSalt(felt!("0x1")), &calldata).unwrap(),
+        cache.get_or_compute(class_hash, salt, &other_calldata).unwrap(),
+    ];
+    assert!(other_addresses.iter().all(|address| *address != expected));
+    assert_eq!(
+        other_addresses[2],
+        calculate_contract_address(salt, class_hash, &other_calldata, ContractAddress::default())
+            .unwrap()
+    );
+    assert_eq!(cache.addresses.len(), 4);
+}
+
+#[test]
+fn well_known_addresses_are_valid() {
+    use crate::core::well_known_addresses::*;
+
+    for address in [
+        ZERO_ADDRESS,
+        BLOCK_HASH_CONTRACT_ADDRESS,
+        MAINNET_ETH_FEE_TOKEN_ADDRESS,
+        SEPOLIA_ETH_FEE_TOKEN_ADDRESS,
+        MAINNET_STRK_FEE_TOKEN_ADDRESS,
+        SEPOLIA_STRK_FEE_TOKEN_ADDRESS,
+        MAINNET_SEQUENCER_ADDRESS,
+    ] {
+        assert_eq!(ContractAddress::try_from(*address.0.key()).unwrap(), address);
+    }
+    assert_eq!(
+        STRK_FEE_TOKEN_ADDRESS,
+        contract_address!("0x4718f5a0fc34cc1af16a1cdee98ffb20c31f5cd61d6ab07201858f4287c938d")
+    );
+}
+
+#[test]
+fn parse_from_hex_str() {
+    assert_eq!(ClassHash::try_from("0x1234").unwrap(), class_hash!("0x1234"));
+    assert_eq!("0x1234".parse::<ContractAddress>().unwrap(), contract_address!("0x1234"));
+    assert_eq!("0x1234".parse::<TransactionHash>().unwrap(), TransactionHash(felt!("0x1234")));
+
+    // Invalid hex.
+    assert_matches!(
+        ClassHash::try_from("1234"),
+        Err(StarknetApiError::InnerDeserialization(InnerDeserializationError::MissingPrefix { .. }))
+    );
+    assert_matches!(
+        "0xg".parse::<TransactionHash>(),
+        Err(StarknetApiError::InnerDeserialization(InnerDeserializationError::FromHex(_)))
+    );
+    // Not a felt.
+    assert_matches!(
+        ClassHash::try_from(format!("0x{}", "f".repeat(64)).as_str()),
+        Err(StarknetApiError::OutOfRange { .. })
+    );
+    // Out of the address range.
+    assert_matches!(
+        ContractAddress::try_from(PATRICIA_KEY_UPPER_BOUND),
+        Err(StarknetApiError::OutOfRange { .. })
+    );
+}
+
+#[test]
+fn from_hex_unchecked_matches_macros() {
+    const KEY: PatriciaKey = PatriciaKey::from_hex_unchecked("0x1234");
+    const ADDRESS: ContractAddress = ContractAddress::from_hex_unchecked("0x1234");
+    const CLASS_HASH: ClassHash = ClassHash::from_hex_unchecked("0x1234");
+    assert_eq!(KEY, patricia_key!("0x1234"));
+    assert_eq!(ADDRESS, contract_address!("0x1234"));
+    assert_eq!(CLASS_HASH, class_hash!("0x1234"));
+}
+
+#[test]
+fn felt_newtypes_display() {
+    assert_eq!(Nonce(felt!("0x2c")).to_string(), "0x2c");
+    assert_eq!(EntryPointSelector(felt!("0x83afd3f4")).to_string(), "0x83afd3f4");
+}
@@ -1,8 +1,9 @@
 // Unittest for verify_message_signature
 
-use starknet_types_core::hash::{Poseidon, StarkHash};
+use starknet_types_core::felt::Felt;
+use starknet_types_core::hash::{Pedersen, Poseidon, StarkHash};
 
-use crate::crypto::utils::{verify_message_hash_signature, PublicKey, Signature};
+use crate::crypto::utils::{verify_message_hash_signature, HashChain, PublicKey, Signature};
 use crate::felt;
 
 #[test]
@@ -24,3 +25,37 @@ fn signature_verification() {
     let result = verify_message_hash_signature(&message_hash, &signature, &public_key).unwrap();
     assert!(result);
 }
+
+#[test]
+fn hash_chain_extend() {
+    let first = HashChain::new().chain(&Felt::ONE).chain(&Felt::TWO);
+    let second = HashChain::new().chain(&Felt::THREE);
+
+    let extended = first.extend(second);
+    let expected = Poseidon::hash_array(&[Felt::ONE, Felt::TWO, Felt::THREE]);
+    assert_eq!(extended.get_poseidon_hash(), expected);
+}
+
+#[test]
+fn hash_chain_size_and_elements() {
+    let felts = [Felt::ONE, Felt::TWO, Felt::THREE];
+
+    let hash_chain = HashChain::new().chain(&Felt::ZERO).chain_size_and_elements(&felts);
+    // The length of the elements is chained before the elements themselves.
+    let expected =
+        Poseidon::hash_array(&[Felt::ZERO, Felt::THREE, Felt::ONE, Felt::TWO, Felt::THREE]);
+    assert_eq!(hash_chain.get_poseidon_hash(), expected);
+
+    let empty_chain = HashChain::new().chain_size_and_elements(&[]);
+    assert_eq!(empty_chain.get_pedersen_hash(), Pedersen::hash_array(&[Felt::ZERO]));
+}
+
+#[test]
+fn public_key_display() {
+    let public_key =
+        PublicKey(felt!("0x48253ff2c3bed7af18bde0b611b083b39445959102d4947c51c4db6aa4f4e58"));
+    assert_eq!(
+        public_key.to_string(),
+        "0x48253ff2c3bed7af18bde0b611b083b39445959102d4947c51c4db6aa4f4e58"
+    );
+}
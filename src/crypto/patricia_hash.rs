@@ -19,6 +19,10 @@
 //! - A leaf: The hash is the input value of its key.
 //! - A single edge: hash(child_hash, edge_mark) + edge_length.
 //! - '0' and '1' edges: hash(zero_child_hash, one_child_hash).
+//!
+//! The tree has a fixed height of 64, i.e., the keys are 64 bits, and isn't padded: only the
+//! input keys are in the tree, so the root depends on the number of values. The empty tree's root
+//! is 0.
 
 #[cfg(test)]
 #[path = "patricia_hash_test.rs"]
@@ -68,6 +72,89 @@ pub fn calculate_root<H: CoreStarkHash>(values: Vec<Felt>) -> Felt {
     get_hash::<H>(SubTree { leaves: &leaves[..], height: 0_u8 })
 }
 
+/// A node on the path from the root of a Patricia tree to one of its leaves, as part of a
+/// membership proof of the leaf.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PatriciaProofNode {
+    /// A node with '0' and '1' edges. Holds the hash of the child that is not on the path.
+    Binary { sibling: Felt },
+    /// A single edge of `length` '0' bits.
+    Edge { length: u8 },
+}
+
+/// Calculates Patricia hash root on the given values, like [calculate_root], along with a
+/// membership proof of the value at the given index: the nodes on the path from the root to the
+/// leaf, ordered from the root. Returns `None` if the index is out of range.
+pub fn calculate_root_with_proof<H: CoreStarkHash>(
+    values: Vec<Felt>,
+    index: usize,
+) -> Option<(Felt, Vec<PatriciaProofNode>)> {
+    if index >= values.len() {
+        return None;
+    }
+    let leaf_key: BitPath = u64::try_from(index).ok()?.to_be_bytes().into();
+    let leaves: Vec<Entry> = values
+        .into_iter()
+        .zip(0u64..)
+        .map(|(felt, idx)| Entry { key: idx.to_be_bytes().into(), value: felt })
+        .collect();
+    let mut proof = Vec::new();
+    let root = get_hash_with_proof::<H>(
+        SubTree { leaves: &leaves[..], height: 0_u8 },
+        &leaf_key,
+        &mut proof,
+    );
+    Some((root, proof))
+}
+
+/// Verifies a membership proof, as returned by [calculate_root_with_proof]: that the leaf is the
+/// value at the given index of a Patricia tree with the given root.
+pub fn verify_patricia_proof<H: CoreStarkHash>(
+    root: Felt,
+    leaf: Felt,
+    index: usize,
+    proof: &[PatriciaProofNode],
+) -> bool {
+    let Ok(index) = u64::try_from(index) else {
+        return false;
+    };
+    let key: BitPath = index.to_be_bytes().into();
+
+    // The heights of the nodes, from the root. The path must go down to the leaves, and the edges
+    // on it must match the key.
+    let mut heights = Vec::with_capacity(proof.len());
+    let mut height = 0_usize;
+    for node in proof {
+        heights.push(height);
+        height += match node {
+            PatriciaProofNode::Binary { .. } => 1,
+            PatriciaProofNode::Edge { length } => usize::from(*length),
+        };
+        if height > usize::from(TREE_HEIGHT) {
+            return false;
+        }
+        if let PatriciaProofNode::Edge { length } = node {
+            if *length == 0 || key[height - usize::from(*length)..height].any() {
+                return false;
+            }
+        }
+    }
+    if height != usize::from(TREE_HEIGHT) {
+        return false;
+    }
+
+    // Hash the path from the leaf up to the root.
+    let mut hash = leaf;
+    for (node, height) in proof.iter().zip(heights).rev() {
+        hash = match node {
+            PatriciaProofNode::Binary { sibling } if key[height] => H::hash(sibling, &hash),
+            PatriciaProofNode::Binary { sibling } => H::hash(&hash, sibling),
+            PatriciaProofNode::Edge { length } => H::hash(&hash, &Felt::ZERO) + Felt::from(*length),
+        };
+    }
+    hash == root
+}
+
 // Recursive hash calculation. There are 3 cases:
 // - Leaf: The sub tree height is maximal. It should contain exactly one entry.
 // - Edge: All the keys start with a longest common ('0's) prefix. NOTE: We assume that the keys are
@@ -107,6 +194,52 @@ fn get_binary_hash<H: CoreStarkHash>(sub_tree: SubTree<'_>, partition_point: usi
     H::hash(&zero_hash, &one_hash)
 }
 
+// Like get_hash, for a sub tree that contains the leaf with the given key. Collects the nodes on
+// the path to the leaf into the proof.
+fn get_hash_with_proof<H: CoreStarkHash>(
+    sub_tree: SubTree<'_>,
+    leaf_key: &BitPath,
+    proof: &mut Vec<PatriciaProofNode>,
+) -> Felt {
+    if sub_tree.height == TREE_HEIGHT {
+        return sub_tree.leaves.first().expect("a leaf should not be empty").value;
+    }
+    match get_splitting(&sub_tree) {
+        SubTreeSplitting::CommonZerosPrefix(n_zeros) => {
+            proof.push(PatriciaProofNode::Edge { length: n_zeros });
+            let child_hash = get_hash_with_proof::<H>(
+                SubTree { leaves: sub_tree.leaves, height: sub_tree.height + n_zeros },
+                leaf_key,
+                proof,
+            );
+            H::hash(&child_hash, &Felt::ZERO) + Felt::from(n_zeros)
+        }
+        SubTreeSplitting::PartitionPoint(partition_point) => {
+            let (zero_leaves, one_leaves) = sub_tree.leaves.split_at(partition_point);
+            let height = sub_tree.height + 1;
+            if leaf_key[usize::from(sub_tree.height)] {
+                let sibling = get_hash::<H>(SubTree { leaves: zero_leaves, height });
+                proof.push(PatriciaProofNode::Binary { sibling });
+                let one_hash = get_hash_with_proof::<H>(
+                    SubTree { leaves: one_leaves, height },
+                    leaf_key,
+                    proof,
+                );
+                H::hash(&sibling, &one_hash)
+            } else {
+                let sibling = get_hash::<H>(SubTree { leaves: one_leaves, height });
+                proof.push(PatriciaProofNode::Binary { sibling });
+                let zero_hash = get_hash_with_proof::<H>(
+                    SubTree { leaves: zero_leaves, height },
+                    leaf_key,
+                    proof,
+                );
+                H::hash(&zero_hash, &sibling)
+            }
+        }
+    }
+}
+
 // Returns the manner the keys of a subtree are splitting: some keys start with '1' or all keys
 // start with '0'.
 fn get_splitting(sub_tree: &SubTree<'_>) -> SubTreeSplitting {
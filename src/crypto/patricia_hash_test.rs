@@ -1,7 +1,9 @@
 use starknet_types_core::felt::Felt;
-use starknet_types_core::hash::Poseidon;
+use starknet_types_core::hash::{Poseidon, StarkHash};
 
-use super::calculate_root;
+use super::{
+    calculate_root, calculate_root_with_proof, verify_patricia_proof, PatriciaProofNode,
+};
 use crate::felt;
 
 #[test]
@@ -25,3 +27,66 @@ fn test_binary_patricia_regression() {
     let expected_root = felt!("0x1c1ba983ee0a0de87d87d67ea3cbee7023aa65f6b7bcf71259f122ea3af80bf");
     assert_eq!(root, expected_root);
 }
+
+#[test]
+fn test_patricia_root_with_proof() {
+    let values = vec![Felt::from(1_u8), Felt::from(2_u8), Felt::from(3_u8)];
+    let expected_root = calculate_root::<Poseidon>(values.clone());
+
+    // The keys 0, 1 and 2 share a prefix of 62 '0' bits. Then, 0 and 1 split from 2, which is
+    // followed by a single '0' bit.
+    let (root, proof) = calculate_root_with_proof::<Poseidon>(values.clone(), 2).unwrap();
+    assert_eq!(root, expected_root);
+    let zero_child_hash = Poseidon::hash(&Felt::from(1_u8), &Felt::from(2_u8));
+    assert_eq!(
+        proof,
+        vec![
+            PatriciaProofNode::Edge { length: 62 },
+            PatriciaProofNode::Binary { sibling: zero_child_hash },
+            PatriciaProofNode::Edge { length: 1 },
+        ]
+    );
+    // Hash the path from the leaf up to the root.
+    let one_child_hash = Poseidon::hash(&Felt::from(3_u8), &Felt::ZERO) + Felt::ONE;
+    let binary_hash = Poseidon::hash(&zero_child_hash, &one_child_hash);
+    assert_eq!(Poseidon::hash(&binary_hash, &Felt::ZERO) + Felt::from(62_u8), root);
+
+    for index in 0..values.len() {
+        let (root, _) = calculate_root_with_proof::<Poseidon>(values.clone(), index).unwrap();
+        assert_eq!(root, expected_root);
+    }
+    assert!(calculate_root_with_proof::<Poseidon>(values, 3).is_none());
+}
+
+#[test]
+fn test_verify_patricia_proof() {
+    for n_leaves in 1_u8..=9 {
+        let values: Vec<Felt> = (1..=n_leaves).map(Felt::from).collect();
+        for (index, leaf) in values.iter().enumerate() {
+            let (root, proof) =
+                calculate_root_with_proof::<Poseidon>(values.clone(), index).unwrap();
+            assert!(verify_patricia_proof::<Poseidon>(root, *leaf, index, &proof));
+
+            // A tampered leaf.
+            assert!(!verify_patricia_proof::<Poseidon>(root, leaf + Felt::ONE, index, &proof));
+            // Another index.
+            assert!(!verify_patricia_proof::<Poseidon>(root, *leaf, index + 1, &proof));
+            // A tampered sibling.
+            let mut tampered_proof = proof.clone();
+            if let Some(PatriciaProofNode::Binary { sibling }) = tampered_proof
+                .iter_mut()
+                .find(|node| matches!(node, PatriciaProofNode::Binary { .. }))
+            {
+                *sibling += Felt::ONE;
+                assert!(!verify_patricia_proof::<Poseidon>(root, *leaf, index, &tampered_proof));
+            }
+            // A truncated proof.
+            assert!(!verify_patricia_proof::<Poseidon>(
+                root,
+                *leaf,
+                index,
+                &proof[..proof.len() - 1]
+            ));
+        }
+    }
+}
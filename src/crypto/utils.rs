@@ -13,10 +13,11 @@ use starknet_types_core::felt::Felt;
 use starknet_types_core::hash::{Pedersen, Poseidon, StarkHash as CoreStarkHash};
 
 use crate::hash::StarkHash;
+use crate::impl_hex_display;
 
 /// An error that can occur during cryptographic operations.
 
-#[derive(thiserror::Error, Clone, Debug)]
+#[derive(thiserror::Error, Clone, Debug, PartialEq)]
 pub enum CryptoError {
     #[error("Invalid public key {0:#x}.")]
     InvalidPublicKey(PublicKey),
@@ -34,6 +35,8 @@ pub enum CryptoError {
 )]
 pub struct PublicKey(pub Felt);
 
+impl_hex_display!(PublicKey);
+
 impl LowerHex for PublicKey {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt::LowerHex::fmt(&self.0, f)
@@ -44,8 +47,11 @@ impl LowerHex for PublicKey {
 #[derive(
     Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord,
 )]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Signature {
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::json_schema::FeltAsHex"))]
     pub r: Felt,
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::json_schema::FeltAsHex"))]
     pub s: Felt,
 }
 
@@ -1,15 +1,33 @@
+#[cfg(test)]
+#[path = "data_availability_test.rs"]
+mod data_availability_test;
+
 use serde::{Deserialize, Serialize};
 use starknet_types_core::felt::Felt;
 
 use crate::StarknetApiError;
 
 #[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "borsh", borsh(use_discriminant = true))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(try_from = "Deserializer")]
 pub enum DataAvailabilityMode {
     L1 = 0,
     L2 = 1,
 }
 
+impl DataAvailabilityMode {
+    /// Returns the index of the mode, as packed in the hash of V3 transactions.
+    pub fn index(&self) -> u64 {
+        match self {
+            DataAvailabilityMode::L1 => 0,
+            DataAvailabilityMode::L2 => 1,
+        }
+    }
+}
+
 /// Deserialize a `DataAvailabilityMode` from a given `Deserializer`.
 ///
 /// This implementation supports deserializing the `DataAvailabilityMode` enum from both numerical
@@ -67,9 +85,35 @@ impl From<DataAvailabilityMode> for Felt {
 #[derive(
     Clone, Default, Copy, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize,
 )]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum L1DataAvailabilityMode {
     #[default]
     Calldata,
     Blob,
 }
+
+impl L1DataAvailabilityMode {
+    /// The bit that marks blob data availability in the commitment byte.
+    const BLOB_COMMITMENT_BIT: u8 = 0b1000_0000;
+
+    /// Returns the byte that encodes the mode in the concatenated counts of the block hash.
+    pub fn as_commitment_byte(&self) -> u8 {
+        match self {
+            L1DataAvailabilityMode::Calldata => 0,
+            L1DataAvailabilityMode::Blob => Self::BLOB_COMMITMENT_BIT,
+        }
+    }
+
+    /// Returns the mode encoded by [L1DataAvailabilityMode::as_commitment_byte].
+    pub fn from_commitment_byte(byte: u8) -> Result<Self, StarknetApiError> {
+        match byte {
+            0 => Ok(L1DataAvailabilityMode::Calldata),
+            Self::BLOB_COMMITMENT_BIT => Ok(L1DataAvailabilityMode::Blob),
+            _ => Err(StarknetApiError::OutOfRange {
+                string: format!("Invalid L1 data availability mode byte: {byte:#010b}."),
+            }),
+        }
+    }
+}
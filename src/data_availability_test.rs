@@ -0,0 +1,17 @@
+use assert_matches::assert_matches;
+
+use crate::data_availability::L1DataAvailabilityMode;
+use crate::StarknetApiError;
+
+#[test]
+fn l1_data_availability_mode_commitment_byte_round_trip() {
+    for mode in [L1DataAvailabilityMode::Calldata, L1DataAvailabilityMode::Blob] {
+        let byte = mode.as_commitment_byte();
+        assert_eq!(L1DataAvailabilityMode::from_commitment_byte(byte), Ok(mode));
+    }
+    assert_eq!(L1DataAvailabilityMode::Blob.as_commitment_byte(), 0b1000_0000);
+    assert_matches!(
+        L1DataAvailabilityMode::from_commitment_byte(1),
+        Err(StarknetApiError::OutOfRange { .. })
+    );
+}
@@ -1,28 +1,254 @@
-use std::collections::HashMap;
+#[cfg(test)]
+#[path = "deprecated_contract_class_test.rs"]
+mod deprecated_contract_class_test;
+
+use std::collections::BTreeMap;
+use std::io::{self, Read};
 use std::num::ParseIntError;
 
 use cairo_lang_starknet_classes::casm_contract_class::CasmContractEntryPoint;
 use itertools::Itertools;
 use serde::de::Error as DeserializationError;
+use serde::ser::SerializeMap;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::ser::Formatter;
 use serde_json::Value;
+use starknet_types_core::felt::Felt;
 
-use crate::core::EntryPointSelector;
-use crate::hash::StarkHash;
+use crate::core::{encode_shortstring, ClassHash, EntryPointSelector};
+use crate::crypto::utils::HashChain;
+use crate::hash::{starknet_keccak_hash, StarkHash};
 use crate::serde_utils::deserialize_optional_contract_class_abi_entry_vector;
+pub use crate::state::{EntryPointType, EntryPointsByType};
 use crate::StarknetApiError;
 
+// The version of the deprecated class hash computation.
+const API_VERSION: Felt = Felt::ZERO;
+
 /// A deprecated contract class.
 #[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ContractClass {
     // Starknet does not verify the abi. If we can't parse it, we set it to None.
     #[serde(default, deserialize_with = "deserialize_optional_contract_class_abi_entry_vector")]
     pub abi: Option<Vec<ContractClassAbiEntry>>,
     pub program: Program,
     /// The selector of each entry point is a unique identifier in the program.
-    // TODO: Consider changing to IndexMap, since this is used for computing the
-    // class hash.
-    pub entry_points_by_type: HashMap<EntryPointType, Vec<EntryPoint>>,
+    pub entry_points_by_type: EntryPointsByType<EntryPoint>,
+}
+
+impl ContractClass {
+    /// Computes the (deprecated) class hash of the contract class.
+    pub fn class_hash(&self) -> Result<ClassHash, StarknetApiError> {
+        let entry_points_hash = |entry_point_type: EntryPointType| {
+            self.entry_points_by_type
+                .get(entry_point_type)
+                .iter()
+                .fold(HashChain::new(), |hash_chain, entry_point| {
+                    hash_chain
+                        .chain(&entry_point.selector.0)
+                        .chain(&Felt::from(entry_point.offset.0))
+                })
+                .get_pedersen_hash()
+        };
+
+        let builtins = self
+            .program
+            .builtins_list()?
+            .iter()
+            .map(|builtin| encode_shortstring(builtin))
+            .collect::<Result<Vec<_>, _>>()?;
+        let bytecode = self.program.bytecode()?;
+
+        Ok(ClassHash(
+            HashChain::new()
+                .chain(&API_VERSION)
+                .chain(&entry_points_hash(EntryPointType::External))
+                .chain(&entry_points_hash(EntryPointType::L1Handler))
+                .chain(&entry_points_hash(EntryPointType::Constructor))
+                .chain(&HashChain::new().chain_iter(builtins.iter()).get_pedersen_hash())
+                .chain(&self.hinted_class_hash()?)
+                .chain(&HashChain::new().chain_iter(bytecode.iter()).get_pedersen_hash())
+                .get_pedersen_hash(),
+        ))
+    }
+
+    // Returns the starknet-keccak of the class' hash preimage JSON.
+    fn hinted_class_hash(&self) -> Result<StarkHash, StarknetApiError> {
+        Ok(starknet_keccak_hash(self.to_hash_preimage_json()?.as_bytes()))
+    }
+
+    /// Returns the JSON of the class that is hashed into its class hash: the program, without
+    /// debug info, and the ABI, serialized the same way as Python's
+    /// `json.dumps(..., sort_keys=True)`.
+    pub fn to_hash_preimage_json(&self) -> Result<String, StarknetApiError> {
+        let mut program = serde_json::to_value(&self.program)
+            .map_err(|err| class_hash_error(&err.to_string()))?;
+        let program_map =
+            program.as_object_mut().ok_or_else(|| class_hash_error("program is not a mapping"))?;
+
+        program_map.insert("debug_info".to_string(), Value::Null);
+        let is_legacy_compiler =
+            program_map.get("compiler_version").unwrap_or(&Value::Null).is_null();
+        if is_legacy_compiler {
+            program_map.remove("compiler_version");
+            // Compilers prior to 0.10.0 formatted named tuple types with an extra space.
+            for key in ["identifiers", "reference_manager"] {
+                if let Some(value) = program_map.get_mut(key) {
+                    add_space_to_cairo_named_tuples(value);
+                }
+            }
+        }
+        match program_map.get_mut("attributes") {
+            Some(Value::Array(attributes)) if !attributes.is_empty() => {
+                for attribute in attributes.iter_mut().filter_map(Value::as_object_mut) {
+                    if attribute
+                        .get("accessible_scopes")
+                        .and_then(Value::as_array)
+                        .is_some_and(Vec::is_empty)
+                    {
+                        attribute.remove("accessible_scopes");
+                    }
+                    if attribute.get("flow_tracking_data").is_some_and(Value::is_null) {
+                        attribute.remove("flow_tracking_data");
+                    }
+                }
+            }
+            _ => {
+                program_map.remove("attributes");
+            }
+        }
+
+        if program_map.values().any(contains_float) {
+            return Err(class_hash_error(
+                "program has integers beyond 64 bits; enable the arbitrary_precision feature",
+            ));
+        }
+
+        let abi =
+            serde_json::to_value(&self.abi).map_err(|err| class_hash_error(&err.to_string()))?;
+        let preimage = HashPreimage { abi, program: HashPreimageProgram(program_map) };
+
+        let mut serialized = Vec::new();
+        preimage
+            .serialize(&mut serde_json::Serializer::with_formatter(
+                &mut serialized,
+                PythonJsonFormatter,
+            ))
+            .map_err(|err| class_hash_error(&err.to_string()))?;
+        String::from_utf8(serialized).map_err(|err| class_hash_error(&err.to_string()))
+    }
+}
+
+fn class_hash_error(message: &str) -> StarknetApiError {
+    StarknetApiError::ClassHashComputation(message.to_string())
+}
+
+// Rewrites the named tuple types in the `cairo_type` and `value` strings under the given value
+// from "(a: felt)" to "(a : felt)". Strings that already use the old syntax are left as is.
+fn add_space_to_cairo_named_tuples(value: &mut Value) {
+    match value {
+        Value::Array(elements) => elements.iter_mut().for_each(add_space_to_cairo_named_tuples),
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                match value {
+                    Value::String(type_str) if key == "cairo_type" || key == "value" => {
+                        *type_str = type_str.replace(": ", " : ").replace("  :", " :");
+                    }
+                    _ => add_space_to_cairo_named_tuples(value),
+                }
+            }
+        }
+        Value::Null | Value::Bool(_) | Value::Number(_) | Value::String(_) => {}
+    }
+}
+
+// Cairo 0 programs have no floats; a float is an integer that didn't fit in 64 bits, whose exact
+// value was lost in deserialization.
+fn contains_float(value: &Value) -> bool {
+    match value {
+        Value::Number(number) => number.is_f64(),
+        Value::Array(elements) => elements.iter().any(contains_float),
+        Value::Object(map) => map.values().any(contains_float),
+        Value::Null | Value::Bool(_) | Value::String(_) => false,
+    }
+}
+
+// The JSON hashed into the hinted class hash. The keys of all the mappings are sorted, as in
+// Python's `json.dumps(..., sort_keys=True)`.
+#[derive(Serialize)]
+struct HashPreimage<'a> {
+    abi: Value,
+    program: HashPreimageProgram<'a>,
+}
+
+// A program mapping, whose hints are sorted by their (integer) PCs, as Python does for `int` keys.
+struct HashPreimageProgram<'a>(&'a serde_json::Map<String, Value>);
+
+impl Serialize for HashPreimageProgram<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (key, value) in self.0 {
+            if key == "hints" {
+                map.serialize_entry(key, &SortedHints(value))?;
+            } else {
+                map.serialize_entry(key, value)?;
+            }
+        }
+        map.end()
+    }
+}
+
+struct SortedHints<'a>(&'a Value);
+
+impl Serialize for SortedHints<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_hints_sorted(self.0, serializer)
+    }
+}
+
+// Formats JSON as Python's `json.dumps` does: ", " and ": " separators, and non-printable-ASCII
+// characters escaped.
+struct PythonJsonFormatter;
+
+impl Formatter for PythonJsonFormatter {
+    fn begin_array_value<W: ?Sized + io::Write>(
+        &mut self,
+        writer: &mut W,
+        first: bool,
+    ) -> io::Result<()> {
+        if first { Ok(()) } else { writer.write_all(b", ") }
+    }
+
+    fn begin_object_key<W: ?Sized + io::Write>(
+        &mut self,
+        writer: &mut W,
+        first: bool,
+    ) -> io::Result<()> {
+        if first { Ok(()) } else { writer.write_all(b", ") }
+    }
+
+    fn begin_object_value<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(b": ")
+    }
+
+    fn write_string_fragment<W: ?Sized + io::Write>(
+        &mut self,
+        writer: &mut W,
+        fragment: &str,
+    ) -> io::Result<()> {
+        for c in fragment.chars() {
+            if (' '..='~').contains(&c) {
+                write!(writer, "{c}")?;
+            } else {
+                let mut utf16 = [0_u16; 2];
+                for unit in c.encode_utf16(&mut utf16) {
+                    write!(writer, "\\u{unit:04x}")?;
+                }
+            }
+        }
+        Ok(())
+    }
 }
 
 /// A [ContractClass](`crate::deprecated_contract_class::ContractClass`) abi entry.
@@ -30,6 +256,7 @@ pub struct ContractClass {
 // untagged enums). We care about the order of the fields in the serialization because it affects
 // the class hash calculation.
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(deny_unknown_fields, untagged)]
 pub enum ContractClassAbiEntry {
     Constructor(FunctionAbiEntry<ConstructorType>),
@@ -42,6 +269,7 @@ pub enum ContractClassAbiEntry {
 /// An event abi entry.
 // The members of the struct are sorted lexicographically for correct hash computation.
 #[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct EventAbiEntry {
     pub data: Vec<TypedParameter>,
     pub keys: Vec<TypedParameter>,
@@ -50,6 +278,7 @@ pub struct EventAbiEntry {
 }
 
 #[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum EventType {
     #[default]
@@ -58,6 +287,7 @@ pub enum EventType {
 
 /// A function abi entry.
 #[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct FunctionAbiEntry<TYPE> {
     pub inputs: Vec<TypedParameter>,
     pub name: String,
@@ -67,7 +297,16 @@ pub struct FunctionAbiEntry<TYPE> {
     pub r#type: TYPE,
 }
 
+impl<TYPE> FunctionAbiEntry<TYPE> {
+    /// Returns the selector of the entry point of the function, i.e., the Starknet Keccak hash of
+    /// its name.
+    pub fn selector(&self) -> EntryPointSelector {
+        EntryPointSelector(starknet_keccak_hash(self.name.as_bytes()))
+    }
+}
+
 #[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum FunctionType {
     #[default]
@@ -75,6 +314,7 @@ pub enum FunctionType {
 }
 
 #[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum ConstructorType {
     #[default]
@@ -82,6 +322,7 @@ pub enum ConstructorType {
 }
 
 #[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum L1HandlerType {
     #[default]
@@ -90,6 +331,7 @@ pub enum L1HandlerType {
 
 /// A function state mutability.
 #[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum FunctionStateMutability {
     #[serde(rename = "view")]
     #[default]
@@ -98,6 +340,7 @@ pub enum FunctionStateMutability {
 
 /// A struct abi entry.
 #[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct StructAbiEntry {
     pub members: Vec<StructMember>,
     pub name: String,
@@ -105,7 +348,37 @@ pub struct StructAbiEntry {
     pub r#type: StructType,
 }
 
+impl StructAbiEntry {
+    /// Returns the member with the given name, if there is one.
+    pub fn member_by_name(&self, name: &str) -> Option<&StructMember> {
+        self.members.iter().find(|member| member.name == name)
+    }
+
+    /// Returns the size of the struct, in felts, after validating the member offsets: the first
+    /// one is 0, they are distinct, and the last one is smaller than the declared size. Members
+    /// have no size of their own, so gaps between the offsets are not detected.
+    pub fn total_size(&self) -> Result<usize, StarknetApiError> {
+        let invalid_layout =
+            |reason: &str| StarknetApiError::InvalidAbi(format!("struct {}: {reason}", self.name));
+        let offsets = self.members.iter().map(|member| member.offset).sorted().collect_vec();
+        let Some((first_offset, last_offset)) = offsets.first().zip(offsets.last()) else {
+            return Ok(self.size);
+        };
+        if *first_offset != 0 {
+            return Err(invalid_layout("the first member isn't at offset 0"));
+        }
+        if offsets.iter().tuple_windows().any(|(offset, next_offset)| offset == next_offset) {
+            return Err(invalid_layout("members overlap"));
+        }
+        if *last_offset >= self.size {
+            return Err(invalid_layout("members exceed the size of the struct"));
+        }
+        Ok(self.size)
+    }
+}
+
 #[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum StructType {
     #[default]
@@ -115,6 +388,7 @@ pub enum StructType {
 /// A struct member for [StructAbiEntry](`crate::deprecated_contract_class::StructAbiEntry`).
 // The members of the struct are sorted lexicographically for correct hash computation.
 #[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct StructMember {
     pub name: String,
     pub offset: usize,
@@ -123,6 +397,7 @@ pub struct StructMember {
 
 /// A program corresponding to a [ContractClass](`crate::deprecated_contract_class::ContractClass`).
 #[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Program {
     #[serde(default)]
     pub attributes: serde_json::Value,
@@ -140,6 +415,68 @@ pub struct Program {
     pub reference_manager: serde_json::Value,
 }
 
+impl Program {
+    /// Deserializes a program from JSON, streamed from the reader.
+    pub fn from_reader(reader: impl Read) -> Result<Self, StarknetApiError> {
+        serde_json::from_reader(reader).map_err(|err| invalid_program(err.to_string()))
+    }
+
+    /// Returns the program bytecode, parsed from the hex strings in the `data` field.
+    pub fn bytecode(&self) -> Result<Vec<Felt>, StarknetApiError> {
+        self.data
+            .as_array()
+            .ok_or_else(|| invalid_program(format!("data is not a list: {}", self.data)))?
+            .iter()
+            .enumerate()
+            .map(|(i, word)| {
+                word.as_str().and_then(|word| Felt::from_hex(word).ok()).ok_or_else(|| {
+                    invalid_program(format!("bytecode word {i} is not a hex felt: {word}"))
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the names of the builtins used by the program.
+    pub fn builtins_list(&self) -> Result<Vec<String>, StarknetApiError> {
+        self.builtins
+            .as_array()
+            .ok_or_else(|| invalid_program(format!("builtins is not a list: {}", self.builtins)))?
+            .iter()
+            .enumerate()
+            .map(|(i, builtin)| {
+                builtin.as_str().map(str::to_owned).ok_or_else(|| {
+                    invalid_program(format!("builtin {i} is not a string: {builtin}"))
+                })
+            })
+            .collect()
+    }
+}
+
+/// The parts of a [Program] needed to run it: its builtins, bytecode and hints.
+///
+/// Unlike [Program], it's deserialized without a JSON value of the entire program, which costs a
+/// lot of memory for large programs. The other fields of the program, e.g., its debug info, are
+/// skipped while parsing.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize)]
+pub struct ProgramBytecode {
+    pub builtins: Vec<String>,
+    pub data: Vec<Felt>,
+    /// The hints of the program, by the pc they are attached to.
+    #[serde(default)]
+    pub hints: BTreeMap<u64, Vec<Value>>,
+}
+
+impl ProgramBytecode {
+    /// Deserializes the bytecode of a program from its JSON, streamed from the reader.
+    pub fn from_reader(reader: impl Read) -> Result<Self, StarknetApiError> {
+        serde_json::from_reader(reader).map_err(|err| invalid_program(err.to_string()))
+    }
+}
+
+fn invalid_program(message: String) -> StarknetApiError {
+    StarknetApiError::InvalidProgram(message)
+}
+
 // Serialize hints as a sorted mapping for correct hash computation.
 fn serialize_hints_sorted<S>(hints: &serde_json::Value, serializer: S) -> Result<S::Ok, S::Error>
 where
@@ -164,26 +501,9 @@ where
     )
 }
 
-/// An entry point type of a [ContractClass](`crate::deprecated_contract_class::ContractClass`).
-#[derive(
-    Debug, Default, Clone, Copy, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord,
-)]
-#[serde(deny_unknown_fields)]
-pub enum EntryPointType {
-    /// A constructor entry point.
-    #[serde(rename = "CONSTRUCTOR")]
-    Constructor,
-    /// An external4 entry point.
-    #[serde(rename = "EXTERNAL")]
-    #[default]
-    External,
-    /// An L1 handler entry point.
-    #[serde(rename = "L1_HANDLER")]
-    L1Handler,
-}
-
 /// An entry point of a [ContractClass](`crate::deprecated_contract_class::ContractClass`).
 #[derive(Debug, Default, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct EntryPoint {
     pub selector: EntryPointSelector,
     pub offset: EntryPointOffset,
@@ -201,6 +521,7 @@ impl TryFrom<CasmContractEntryPoint> for EntryPoint {
 }
 
 #[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct TypedParameter {
     pub name: String,
     pub r#type: String,
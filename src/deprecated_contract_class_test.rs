@@ -0,0 +1,218 @@
+use assert_matches::assert_matches;
+use starknet_types_core::felt::Felt;
+
+use super::{
+    ContractClass, FunctionAbiEntry, FunctionType, Program, ProgramBytecode, StructAbiEntry,
+    StructMember,
+};
+use crate::core::EntryPointSelector;
+use crate::{felt, StarknetApiError};
+
+#[cfg(feature = "arbitrary_precision")]
+#[test]
+fn deprecated_class_hash() {
+    let contract_class: ContractClass = serde_json::from_str(include_str!(
+        "../resources/account_with_dummy_validate_compiled.json"
+    ))
+    .unwrap();
+    let expected_hash = felt!("0x16dc3038da22dde8ad61a786ab9930699cc496c8bccb90d77cc8abee89803f7");
+    assert_eq!(contract_class.class_hash().unwrap(), crate::core::ClassHash(expected_hash));
+}
+
+#[cfg(not(feature = "arbitrary_precision"))]
+#[test]
+fn deprecated_class_hash_requires_arbitrary_precision() {
+    // The program has constants beyond 64 bits.
+    let contract_class: ContractClass = serde_json::from_str(include_str!(
+        "../resources/account_with_dummy_validate_compiled.json"
+    ))
+    .unwrap();
+    assert_matches!(contract_class.class_hash(), Err(StarknetApiError::ClassHashComputation(_)));
+}
+
+#[cfg(feature = "arbitrary_precision")]
+#[test]
+fn hash_preimage_json_is_stable() {
+    let contract_class: ContractClass = serde_json::from_str(include_str!(
+        "../resources/account_with_dummy_validate_compiled.json"
+    ))
+    .unwrap();
+    let preimage = contract_class.to_hash_preimage_json().unwrap();
+    assert_eq!(
+        crate::hash::starknet_keccak_hash(preimage.as_bytes()),
+        contract_class.hinted_class_hash().unwrap()
+    );
+    assert!(preimage.contains(r#""debug_info": null"#));
+
+    let reserialized: ContractClass =
+        serde_json::from_str(&serde_json::to_string(&contract_class).unwrap()).unwrap();
+    assert_eq!(reserialized.to_hash_preimage_json().unwrap(), preimage);
+}
+
+#[test]
+fn hash_preimage_json_sorts_only_hints_numerically() {
+    let mut program = program_for_testing(serde_json::json!([]), serde_json::json!([]));
+    program.hints = serde_json::json!({"10": [], "9": []});
+    program.identifiers = serde_json::json!({"10": {}, "9": {}});
+    let contract_class = ContractClass { program, ..Default::default() };
+
+    let preimage = contract_class.to_hash_preimage_json().unwrap();
+    assert!(preimage.contains(r#""hints": {"9": [], "10": []}"#));
+    assert!(preimage.contains(r#""identifiers": {"10": {}, "9": {}}"#));
+}
+
+#[test]
+fn hash_preimage_json_of_legacy_program() {
+    let mut program = program_for_testing(serde_json::json!([]), serde_json::json!([]));
+    program.identifiers = serde_json::json!({
+        "__main__.Point": {
+            "members": {"inner": {"cairo_type": "(a: felt, b : felt)", "offset": 0}},
+            "size": 2,
+            "type": "struct"
+        },
+        "__main__.foo.p": {
+            "cairo_type": "(a: felt)*",
+            "references": [{"pc": 0, "value": "cast([fp + (-3)], (a: felt)*)"}],
+            "type": "reference"
+        }
+    });
+    program.reference_manager = serde_json::json!({
+        "references": [{"pc": 0, "value": "cast([fp + (-3)], (a : felt)*)"}]
+    });
+    let contract_class = ContractClass { program, ..Default::default() };
+
+    let preimage = contract_class.to_hash_preimage_json().unwrap();
+    assert!(!preimage.contains("compiler_version"));
+    assert!(preimage.contains(r#""cairo_type": "(a : felt, b : felt)""#));
+    assert!(preimage.contains(r#""cairo_type": "(a : felt)*""#));
+    assert_eq!(preimage.matches(r#""value": "cast([fp + (-3)], (a : felt)*)""#).count(), 2);
+    assert!(!preimage.contains("a: felt"));
+}
+
+fn program_for_testing(data: serde_json::Value, builtins: serde_json::Value) -> Program {
+    serde_json::from_value(serde_json::json!({
+        "builtins": builtins,
+        "data": data,
+        "hints": {},
+        "identifiers": {},
+        "main_scope": "__main__",
+        "prime": "0x800000000000011000000000000000000000000000000000000000000000001",
+        "reference_manager": {"references": []}
+    }))
+    .unwrap()
+}
+
+#[test]
+fn program_bytecode_and_builtins() {
+    let program = program_for_testing(
+        serde_json::json!(["0x40780017fff7fff", "0x1"]),
+        serde_json::json!(["pedersen", "range_check"]),
+    );
+    assert_eq!(program.bytecode().unwrap(), vec![felt!("0x40780017fff7fff"), Felt::ONE]);
+    assert_eq!(program.builtins_list().unwrap(), vec!["pedersen", "range_check"]);
+}
+
+#[test]
+fn program_bytecode_from_reader() {
+    // A moderately large program, with bytecode of 100K words and a hint every 1000 words.
+    let n_words = 100_000_u64;
+    let data: Vec<_> = (0..n_words).map(|i| format!("{:#x}", i * 0x1000193)).collect();
+    let hints: serde_json::Map<_, _> = (0..n_words)
+        .step_by(1000)
+        .map(|pc| (pc.to_string(), serde_json::json!([{"code": format!("hint_{pc}")}])))
+        .collect();
+    let program_json = serde_json::json!({
+        "builtins": ["pedersen", "range_check"],
+        "data": data,
+        "debug_info": {"instruction_locations": {}},
+        "hints": hints,
+        "identifiers": {},
+        "main_scope": "__main__",
+        "prime": "0x800000000000011000000000000000000000000000000000000000000000001",
+        "reference_manager": {"references": []}
+    })
+    .to_string();
+
+    let program_bytecode = ProgramBytecode::from_reader(program_json.as_bytes()).unwrap();
+    let program = Program::from_reader(program_json.as_bytes()).unwrap();
+    assert_eq!(program_bytecode.data.len(), 100_000);
+    assert_eq!(program_bytecode.data[99_999], Felt::from(99_999_u64 * 0x1000193));
+    assert_eq!(program_bytecode.data, program.bytecode().unwrap());
+    assert_eq!(program_bytecode.builtins, program.builtins_list().unwrap());
+    assert_eq!(program_bytecode.hints.len(), 100);
+    assert_eq!(program_bytecode.hints[&99_000], vec![serde_json::json!({"code": "hint_99000"})]);
+
+    assert_matches!(
+        ProgramBytecode::from_reader(r#"{"builtins": [], "data": [7]}"#.as_bytes()),
+        Err(StarknetApiError::InvalidProgram(_))
+    );
+}
+
+#[test]
+fn program_malformed_bytecode_and_builtins() {
+    let program = program_for_testing(serde_json::json!(["0x1", 2]), serde_json::json!([7]));
+    assert_matches!(
+        program.bytecode(),
+        Err(StarknetApiError::InvalidProgram(message)) if message.contains("bytecode word 1")
+    );
+    assert_matches!(
+        program.builtins_list(),
+        Err(StarknetApiError::InvalidProgram(message)) if message.contains("builtin 0")
+    );
+
+    let program = program_for_testing(serde_json::json!("0x1"), serde_json::json!({}));
+    assert_matches!(program.bytecode(), Err(StarknetApiError::InvalidProgram(_)));
+    assert_matches!(program.builtins_list(), Err(StarknetApiError::InvalidProgram(_)));
+}
+
+#[test]
+fn function_abi_entry_selector() {
+    let function = FunctionAbiEntry::<FunctionType> {
+        name: "transfer".to_string(),
+        ..Default::default()
+    };
+    // The selector of the ERC20 transfer function on Mainnet.
+    assert_eq!(
+        function.selector(),
+        EntryPointSelector(felt!(
+            "0x83afd3f4caedc6eebf44246fe54e38c95e3179a5ec9ea81740eca5b482d12e"
+        ))
+    );
+}
+
+fn struct_for_testing(size: usize, offsets: &[usize]) -> StructAbiEntry {
+    StructAbiEntry {
+        members: offsets
+            .iter()
+            .enumerate()
+            .map(|(index, offset)| StructMember {
+                name: format!("member_{index}"),
+                offset: *offset,
+                r#type: "felt".to_string(),
+            })
+            .collect(),
+        name: "Point".to_string(),
+        size,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn struct_abi_entry_layout() {
+    // A struct whose second member takes two felts.
+    let struct_entry = struct_for_testing(3, &[0, 1]);
+    assert_eq!(struct_entry.total_size().unwrap(), 3);
+    assert_eq!(struct_entry.member_by_name("member_1").unwrap().offset, 1);
+    assert!(struct_entry.member_by_name("member_2").is_none());
+    assert_eq!(struct_for_testing(0, &[]).total_size().unwrap(), 0);
+    // Gaps between the members can't be told apart from large members, so they are accepted.
+    assert_eq!(struct_for_testing(6, &[0, 5]).total_size().unwrap(), 6);
+
+    for (size, offsets) in [(2, [1, 0, 2].as_slice()), (2, &[1]), (2, &[0, 0])] {
+        assert_matches!(
+            struct_for_testing(size, offsets).total_size(),
+            Err(StarknetApiError::InvalidAbi(_)),
+            "size: {size}, offsets: {offsets:?}"
+        );
+    }
+}
@@ -1,14 +1,36 @@
+#[cfg(test)]
+#[path = "hash_test.rs"]
+mod hash_test;
+
 use std::fmt::Debug;
 
 use serde::{Deserialize, Serialize};
 use sha3::{Digest, Keccak256};
 use starknet_types_core::felt::Felt;
+use starknet_types_core::hash::{Poseidon, StarkHash as CoreStarkHash};
+
+use crate::impl_hex_display;
 
 pub type StarkHash = Felt;
 
 #[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
 pub struct PoseidonHash(pub Felt);
 
+impl_hex_display!(PoseidonHash);
+
+impl PoseidonHash {
+    /// Computes the Poseidon hash of an array of felts.
+    pub fn from_array(values: &[Felt]) -> Self {
+        Self(Poseidon::hash_array(values))
+    }
+}
+
+impl From<Felt> for PoseidonHash {
+    fn from(felt: Felt) -> Self {
+        Self(felt)
+    }
+}
+
 /// Computes the first 250 bits of the Keccak256 hash, in order to fit into a field element.
 pub fn starknet_keccak_hash(input: &[u8]) -> Felt {
     let mut keccak = Keccak256::default();
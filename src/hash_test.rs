@@ -0,0 +1,44 @@
+use starknet_types_core::felt::Felt;
+use starknet_types_core::hash::{Poseidon, StarkHash as CoreStarkHash};
+
+use crate::core::StateDiffCommitment;
+use crate::felt;
+use crate::hash::PoseidonHash;
+
+#[test]
+fn poseidon_hash_from_array() {
+    let values = [felt!("0x1"), felt!("0x2"), felt!("0x3")];
+    let expected = Poseidon::hash_array(&values);
+    assert_eq!(PoseidonHash::from_array(&values), PoseidonHash(expected));
+
+    // The array hash pads and absorbs the values in pairs, unlike the two-to-one hash.
+    assert_ne!(
+        PoseidonHash::from_array(&[felt!("0x1"), felt!("0x2")]),
+        PoseidonHash(Poseidon::hash(&felt!("0x1"), &felt!("0x2")))
+    );
+    assert_eq!(PoseidonHash::from_array(&[]), PoseidonHash(Poseidon::hash_array(&[])));
+}
+
+#[test]
+fn poseidon_hash_from_felt() {
+    let felt = felt!("0x1234");
+    assert_eq!(PoseidonHash::from(felt), PoseidonHash(felt));
+    assert_eq!(StateDiffCommitment(felt.into()), StateDiffCommitment(PoseidonHash(felt)));
+    assert_eq!(PoseidonHash::from(Felt::ZERO), PoseidonHash::default());
+}
+
+#[test]
+fn crate_root_reexports_hash_types() {
+    // Both paths name the same types, so values pass between them without conversion.
+    let stark_hash: crate::StarkHash = felt!("0x1");
+    let _: crate::hash::StarkHash = stark_hash;
+    let poseidon_hash: crate::PoseidonHash = PoseidonHash(stark_hash);
+    let _: crate::hash::PoseidonHash = poseidon_hash;
+}
+
+#[test]
+fn poseidon_hash_display() {
+    let poseidon_hash = PoseidonHash(felt!("0xabc"));
+    assert_eq!(poseidon_hash.to_string(), "0xabc");
+    assert_eq!(StateDiffCommitment(poseidon_hash).to_string(), "0xabc");
+}
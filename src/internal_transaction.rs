@@ -1,8 +1,14 @@
+#[cfg(test)]
+#[path = "internal_transaction_test.rs"]
+mod internal_transaction_test;
+
 use crate::core::{ContractAddress, Nonce};
 use crate::state::ContractClass;
 use crate::transaction::{
-    DeclareTransaction, DeployAccountTransaction, InvokeTransaction, Tip, TransactionHash,
+    DeclareTransaction, DeployAccountTransaction, InvokeTransaction, Tip, Transaction,
+    TransactionHash,
 };
+use crate::StarknetApiError;
 
 /// Represents a paid Starknet transaction.
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -37,20 +43,50 @@ impl InternalTransaction {
         }
     }
 
+    /// Converts a transaction with the given hash into an internal transaction. The class info is
+    /// required for declare transactions and ignored for others.
+    ///
+    /// Deploy and L1 handler transactions aren't sent by an account, so they can't be converted.
+    pub fn try_from_transaction(
+        tx: Transaction,
+        tx_hash: TransactionHash,
+        class_info: Option<ClassInfo>,
+    ) -> Result<Self, StarknetApiError> {
+        match tx {
+            Transaction::Declare(tx) => {
+                let class_info = class_info.ok_or(StarknetApiError::MissingClassInfo)?;
+                Ok(InternalTransaction::Declare(InternalDeclareTransaction {
+                    tx,
+                    tx_hash,
+                    only_query: false,
+                    class_info,
+                }))
+            }
+            Transaction::DeployAccount(tx) => {
+                let contract_address = tx.calculate_contract_address()?;
+                Ok(InternalTransaction::DeployAccount(InternalDeployAccountTransaction {
+                    tx,
+                    tx_hash,
+                    contract_address,
+                    only_query: false,
+                }))
+            }
+            Transaction::Invoke(tx) => Ok(InternalTransaction::Invoke(InternalInvokeTransaction {
+                tx,
+                tx_hash,
+                only_query: false,
+            })),
+            Transaction::Deploy(_) | Transaction::L1Handler(_) => {
+                Err(StarknetApiError::NonAccountTransaction(tx.transaction_type()))
+            }
+        }
+    }
+
     pub fn tip(&self) -> Option<Tip> {
         match self {
-            InternalTransaction::Declare(declare_tx) => match &declare_tx.tx {
-                DeclareTransaction::V3(tx_v3) => Some(tx_v3.tip),
-                _ => None,
-            },
-            InternalTransaction::DeployAccount(deploy_account_tx) => match &deploy_account_tx.tx {
-                DeployAccountTransaction::V3(tx_v3) => Some(tx_v3.tip),
-                _ => None,
-            },
-            InternalTransaction::Invoke(invoke_tx) => match &invoke_tx.tx {
-                InvokeTransaction::V3(tx_v3) => Some(tx_v3.tip),
-                _ => None,
-            },
+            InternalTransaction::Declare(declare_tx) => declare_tx.tx.tip(),
+            InternalTransaction::DeployAccount(deploy_account_tx) => deploy_account_tx.tx.tip(),
+            InternalTransaction::Invoke(invoke_tx) => invoke_tx.tx.tip(),
         }
     }
 }
@@ -88,3 +124,31 @@ pub struct ClassInfo {
     pub sierra_program_length: usize,
     pub abi_length: usize,
 }
+
+impl ClassInfo {
+    /// Creates the class info of a contract class, with the lengths of its Sierra program and ABI.
+    pub fn new(contract_class: ContractClass) -> Self {
+        let sierra_program_length = contract_class.sierra_program.len();
+        let abi_length = contract_class.abi.len();
+        Self { contract_class, sierra_program_length, abi_length }
+    }
+
+    /// Validates that the lengths of the class info match its contract class.
+    pub fn validate(&self) -> Result<(), StarknetApiError> {
+        let sierra_program_length = self.contract_class.sierra_program.len();
+        if self.sierra_program_length != sierra_program_length {
+            return Err(StarknetApiError::InvalidClassInfo(format!(
+                "Sierra program length is {}, but the class has {sierra_program_length} felts",
+                self.sierra_program_length
+            )));
+        }
+        let abi_length = self.contract_class.abi.len();
+        if self.abi_length != abi_length {
+            return Err(StarknetApiError::InvalidClassInfo(format!(
+                "ABI length is {}, but the class ABI has {abi_length} bytes",
+                self.abi_length
+            )));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,95 @@
+use assert_matches::assert_matches;
+use starknet_types_core::felt::Felt;
+
+use super::{ClassInfo, InternalTransaction};
+use crate::core::{ContractAddress, PatriciaKey};
+use crate::state::ContractClass;
+use crate::transaction::{
+    DeclareTransaction, DeployAccountTransaction, InvokeTransaction, InvokeTransactionV1,
+    Transaction, TransactionHash, TransactionType,
+};
+use crate::{contract_address, felt, patricia_key, StarknetApiError};
+
+#[test]
+fn internal_transaction_from_transaction() {
+    let tx_hash = TransactionHash(felt!("0x1"));
+    let invoke = InvokeTransaction::V1(InvokeTransactionV1 {
+        sender_address: contract_address!("0x2"),
+        ..Default::default()
+    });
+    let internal_tx = InternalTransaction::try_from_transaction(
+        Transaction::Invoke(invoke.clone()),
+        tx_hash,
+        None,
+    )
+    .unwrap();
+    assert_matches!(
+        internal_tx,
+        InternalTransaction::Invoke(ref tx) if tx.tx == invoke && !tx.only_query
+    );
+    assert_eq!(internal_tx.tx_hash(), tx_hash);
+    assert_eq!(internal_tx.contract_address(), contract_address!("0x2"));
+
+    let deploy_account = DeployAccountTransaction::V1(Default::default());
+    let internal_tx = InternalTransaction::try_from_transaction(
+        Transaction::DeployAccount(deploy_account.clone()),
+        tx_hash,
+        None,
+    )
+    .unwrap();
+    assert_eq!(
+        internal_tx.contract_address(),
+        deploy_account.calculate_contract_address().unwrap()
+    );
+
+    // Declare transactions require the declared class.
+    let declare = Transaction::Declare(DeclareTransaction::V2(Default::default()));
+    assert_eq!(
+        InternalTransaction::try_from_transaction(declare.clone(), tx_hash, None),
+        Err(StarknetApiError::MissingClassInfo)
+    );
+    let class_info = ClassInfo::new(ContractClass::default());
+    assert_matches!(
+        InternalTransaction::try_from_transaction(declare, tx_hash, Some(class_info)),
+        Ok(InternalTransaction::Declare(_))
+    );
+}
+
+#[test]
+fn non_account_transactions_are_rejected() {
+    let tx_hash = TransactionHash(felt!("0x1"));
+    assert_eq!(
+        InternalTransaction::try_from_transaction(
+            Transaction::L1Handler(Default::default()),
+            tx_hash,
+            None,
+        ),
+        Err(StarknetApiError::NonAccountTransaction(TransactionType::L1Handler))
+    );
+    assert_eq!(
+        InternalTransaction::try_from_transaction(
+            Transaction::Deploy(Default::default()),
+            tx_hash,
+            None,
+        ),
+        Err(StarknetApiError::NonAccountTransaction(TransactionType::Deploy))
+    );
+}
+
+#[test]
+fn class_info_lengths() {
+    let contract_class = ContractClass {
+        sierra_program: vec![Felt::ONE, Felt::TWO, Felt::THREE],
+        abi: r#"[{"type":"function"}]"#.to_owned(),
+        ..Default::default()
+    };
+    let class_info = ClassInfo::new(contract_class.clone());
+    assert_eq!(class_info.sierra_program_length, 3);
+    assert_eq!(class_info.abi_length, contract_class.abi.len());
+    assert_eq!(class_info.validate(), Ok(()));
+
+    let tampered = ClassInfo { sierra_program_length: 2, ..class_info.clone() };
+    assert_matches!(tampered.validate(), Err(StarknetApiError::InvalidClassInfo(_)));
+    let tampered = ClassInfo { abi_length: 0, ..class_info };
+    assert_matches!(tampered.validate(), Err(StarknetApiError::InvalidClassInfo(_)));
+}
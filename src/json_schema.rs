@@ -0,0 +1,134 @@
+//! [JSON Schema](https://json-schema.org/) implementations for types whose schema can't be derived
+//! from their structure, e.g., felts that are serialized as hex strings.
+#[cfg(test)]
+#[path = "json_schema_test.rs"]
+mod json_schema_test;
+
+use schemars::gen::SchemaGenerator;
+use schemars::schema::{ArrayValidation, InstanceType, Schema, SchemaObject, StringValidation};
+use schemars::JsonSchema;
+
+use crate::block::{BlockHash, GasPrice};
+use crate::core::{
+    ChainId, ClassHash, CompiledClassHash, ContractAddress, EntryPointSelector, EthAddress,
+    EventCommitment, GlobalRoot, Nonce, PatriciaKey, ReceiptCommitment, TransactionCommitment,
+};
+use crate::crypto::utils::PublicKey;
+use crate::deprecated_contract_class::EntryPointOffset;
+use crate::hash::PoseidonHash;
+use crate::transaction::{
+    AccountDeploymentData, Calldata, ContractAddressSalt, EventData, EventKey, Fee, L1ToL2Payload,
+    L2ToL1Payload, PaymasterData, ResourceAmount, ResourcePrice, Tip, TransactionHash,
+    TransactionSignature, TransactionVersion,
+};
+
+/// The pattern of a felt, serialized as a `0x`-prefixed hex string without leading zeros.
+pub const FELT_PATTERN: &str = "^0x(0|[a-fA-F1-9][a-fA-F0-9]{0,62})$";
+/// The pattern of a [PatriciaKey](`crate::core::PatriciaKey`), i.e., a felt below 2^251.
+pub const PATRICIA_KEY_PATTERN: &str = "^0x(0|[a-fA-F1-9][a-fA-F0-9]{0,61}|[1-7][a-fA-F0-9]{62})$";
+/// The pattern of an integer, serialized as a `0x`-prefixed hex string.
+pub const HEX_NUMBER_PATTERN: &str = "^0x[a-fA-F0-9]+$";
+const ETH_ADDRESS_PATTERN: &str = "^0x[a-fA-F0-9]{1,40}$";
+
+/// A felt, for fields of type [Felt](`starknet_types_core::felt::Felt`) in types that derive
+/// [JsonSchema].
+pub struct FeltAsHex;
+
+fn string_schema(pattern: &str) -> Schema {
+    SchemaObject {
+        instance_type: Some(InstanceType::String.into()),
+        string: Some(Box::new(StringValidation {
+            pattern: Some(pattern.to_owned()),
+            ..Default::default()
+        })),
+        ..Default::default()
+    }
+    .into()
+}
+
+fn felt_schema(_gen: &mut SchemaGenerator) -> Schema {
+    string_schema(FELT_PATTERN)
+}
+
+fn patricia_key_schema(_gen: &mut SchemaGenerator) -> Schema {
+    string_schema(PATRICIA_KEY_PATTERN)
+}
+
+fn hex_number_schema(_gen: &mut SchemaGenerator) -> Schema {
+    string_schema(HEX_NUMBER_PATTERN)
+}
+
+fn eth_address_schema(_gen: &mut SchemaGenerator) -> Schema {
+    string_schema(ETH_ADDRESS_PATTERN)
+}
+
+fn chain_id_schema(_gen: &mut SchemaGenerator) -> Schema {
+    SchemaObject { instance_type: Some(InstanceType::String.into()), ..Default::default() }.into()
+}
+
+fn felt_vector_schema(gen: &mut SchemaGenerator) -> Schema {
+    SchemaObject {
+        instance_type: Some(InstanceType::Array.into()),
+        array: Some(Box::new(ArrayValidation {
+            items: Some(gen.subschema_for::<FeltAsHex>().into()),
+            ..Default::default()
+        })),
+        ..Default::default()
+    }
+    .into()
+}
+
+macro_rules! impl_json_schema {
+    ($schema_fn:ident: $($type:ident),+ $(,)?) => {
+        $(
+            impl JsonSchema for $type {
+                fn schema_name() -> String {
+                    stringify!($type).to_owned()
+                }
+
+                fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+                    $schema_fn(gen)
+                }
+            }
+        )+
+    };
+}
+
+impl_json_schema!(
+    felt_schema: FeltAsHex,
+    BlockHash,
+    ClassHash,
+    CompiledClassHash,
+    ContractAddressSalt,
+    EntryPointSelector,
+    EventCommitment,
+    EventKey,
+    GlobalRoot,
+    Nonce,
+    PoseidonHash,
+    PublicKey,
+    ReceiptCommitment,
+    TransactionCommitment,
+    TransactionHash,
+    TransactionVersion,
+);
+impl_json_schema!(patricia_key_schema: ContractAddress, PatriciaKey);
+impl_json_schema!(
+    felt_vector_schema: AccountDeploymentData,
+    Calldata,
+    EventData,
+    L1ToL2Payload,
+    L2ToL1Payload,
+    PaymasterData,
+    TransactionSignature,
+);
+impl_json_schema!(
+    hex_number_schema: EntryPointOffset,
+    Fee,
+    GasPrice,
+    ResourceAmount,
+    ResourcePrice,
+    Tip
+);
+impl_json_schema!(eth_address_schema: EthAddress);
+impl_json_schema!(chain_id_schema: ChainId);
@@ -0,0 +1,11 @@
+use schemars::schema_for;
+
+use super::{FELT_PATTERN, PATRICIA_KEY_PATTERN};
+use crate::rpc_transaction::RpcTransaction;
+
+#[test]
+fn rpc_transaction_schema() {
+    let schema = serde_json::to_string(&schema_for!(RpcTransaction)).unwrap();
+    assert!(schema.contains(&serde_json::to_string(FELT_PATTERN).unwrap()));
+    assert!(schema.contains(&serde_json::to_string(PATRICIA_KEY_PATTERN).unwrap()));
+}
@@ -2,15 +2,25 @@
 //!
 //! [`Starknet`]: https://starknet.io/
 
+#[cfg(feature = "arbitrary")]
+pub mod arbitrary_impls;
 pub mod block;
 pub mod block_hash;
+#[cfg(feature = "borsh")]
+pub mod borsh_codec;
 pub mod core;
 pub mod crypto;
 pub mod data_availability;
 pub mod deprecated_contract_class;
 pub mod hash;
 pub mod internal_transaction;
+#[cfg(feature = "schemars")]
+pub mod json_schema;
+#[cfg(feature = "proptest")]
+pub mod proptest_strategies;
 pub mod rpc_transaction;
+#[cfg(feature = "parity-scale-codec")]
+pub mod scale_codec;
 pub mod serde_utils;
 pub mod state;
 pub mod transaction;
@@ -19,6 +29,8 @@ pub mod type_utils;
 
 use std::num::ParseIntError;
 
+use block::BlockVerificationError;
+pub use hash::{PoseidonHash, StarkHash};
 use serde_utils::InnerDeserializationError;
 
 /// The error type returned by StarknetApi.
@@ -37,4 +49,44 @@ pub enum StarknetApiError {
     /// Missing resource type / duplicated resource type.
     #[error("Missing resource type / duplicated resource type; got {0}.")]
     InvalidResourceMappingInitializer(String),
+    /// Error in the parsing of a deprecated contract class program.
+    #[error("Invalid program: {0}.")]
+    InvalidProgram(String),
+    /// Error in the parsing of the ABI of a contract class.
+    #[error("Invalid ABI: {0}.")]
+    InvalidAbi(String),
+    /// Error in the computation of a class hash.
+    #[error("Failed to compute the class hash: {0}.")]
+    ClassHashComputation(String),
+    /// A chain id that isn't a valid encoded string.
+    #[error("Invalid chain id: {0}.")]
+    InvalidChainId(String),
+    /// A string that is expected to be ASCII but contains other characters.
+    #[error("Expected an ASCII string; got {0:?}.")]
+    NonAsciiString(String),
+    /// An event with too many keys or data elements.
+    #[error("Event {field} size {size} exceeds the maximum of {max_size}.")]
+    EventSizeExceeded { field: &'static str, size: usize, max_size: usize },
+    /// A transaction of a different type than expected.
+    #[error("Expected a {expected} transaction; got {actual}.")]
+    UnexpectedTransactionType {
+        expected: transaction::TransactionType,
+        actual: transaction::TransactionType,
+    },
+    /// A transaction that isn't sent by an account, e.g., an L1 handler transaction, where an
+    /// account transaction is expected.
+    #[error("{0} transactions are not account transactions.")]
+    NonAccountTransaction(transaction::TransactionType),
+    /// A declare transaction given without the class it declares.
+    #[error("Missing the class info of a declare transaction.")]
+    MissingClassInfo,
+    /// Class info whose lengths don't match its contract class.
+    #[error("Invalid class info: {0}.")]
+    InvalidClassInfo(String),
+    /// A malformed event index token.
+    #[error("Invalid event index: {0:?}.")]
+    InvalidEventIndex(String),
+    /// Error in the verification of a block.
+    #[error(transparent)]
+    BlockVerification(#[from] BlockVerificationError),
 }
@@ -0,0 +1,117 @@
+//! [Proptest](https://docs.rs/proptest) strategies for generating valid Starknet types.
+#[cfg(test)]
+#[path = "proptest_strategies_test.rs"]
+mod proptest_strategies_test;
+
+use std::fmt::Debug;
+use std::hash::Hash;
+
+use indexmap::IndexMap;
+use proptest::collection::btree_map;
+use proptest::prelude::*;
+use starknet_types_core::felt::Felt;
+
+use crate::core::{ClassHash, CompiledClassHash, ContractAddress, Nonce, PatriciaKey};
+use crate::deprecated_contract_class::ContractClass as DeprecatedContractClass;
+use crate::state::{ContractClass, StateDiff, StorageKey};
+
+/// The maximal number of entries in each of the maps of a generated [StateDiff].
+pub const MAX_STATE_DIFF_MAP_SIZE: usize = 8;
+
+/// Returns a strategy that generates any [Felt].
+pub fn felt_strategy() -> impl Strategy<Value = Felt> {
+    any::<[u8; 32]>().prop_map(|bytes| Felt::from_bytes_be(&bytes))
+}
+
+/// Returns a strategy that generates a [PatriciaKey], i.e., a felt smaller than
+/// [PATRICIA_KEY_UPPER_BOUND](`crate::core::PATRICIA_KEY_UPPER_BOUND`).
+pub fn patricia_key_strategy() -> impl Strategy<Value = PatriciaKey> {
+    any::<[u8; 32]>().prop_map(|mut bytes| {
+        // Clear the 5 most significant bits, so the key is smaller than 2^251.
+        bytes[0] &= 0x07;
+        PatriciaKey::try_from(Felt::from_bytes_be(&bytes))
+            .expect("A 251 bit key should be in range.")
+    })
+}
+
+/// Returns a strategy that generates a [ContractAddress].
+pub fn contract_address_strategy() -> impl Strategy<Value = ContractAddress> {
+    patricia_key_strategy().prop_map(ContractAddress)
+}
+
+/// Returns a strategy that generates a [StorageKey].
+pub fn storage_key_strategy() -> impl Strategy<Value = StorageKey> {
+    patricia_key_strategy().prop_map(StorageKey)
+}
+
+/// Returns a strategy that generates a [Nonce].
+pub fn nonce_strategy() -> impl Strategy<Value = Nonce> {
+    felt_strategy().prop_map(Nonce)
+}
+
+/// Returns a strategy that generates a [ClassHash].
+pub fn class_hash_strategy() -> impl Strategy<Value = ClassHash> {
+    felt_strategy().prop_map(ClassHash)
+}
+
+/// Returns a strategy that generates a [StateDiff] that respects its invariants:
+/// - The addresses (and storage keys) are strictly increasing.
+/// - The class hashes of the declared classes and the deprecated declared classes are disjoint.
+///
+/// The generated classes are empty, as they don't take part in the state diff commitment.
+pub fn state_diff_strategy() -> impl Strategy<Value = StateDiff> {
+    (
+        address_map_strategy(class_hash_strategy()),
+        address_map_strategy(sorted_index_map_strategy(storage_key_strategy(), felt_strategy())),
+        // Each class hash is either declared (with its compiled class hash) or deprecated.
+        btree_map(
+            class_hash_strategy(),
+            proptest::option::of(felt_strategy().prop_map(CompiledClassHash)),
+            0..MAX_STATE_DIFF_MAP_SIZE,
+        ),
+        address_map_strategy(nonce_strategy()),
+        address_map_strategy(class_hash_strategy()),
+    )
+        .prop_map(|(deployed_contracts, storage_diffs, classes, nonces, replaced_classes)| {
+            let mut state_diff = StateDiff {
+                deployed_contracts,
+                storage_diffs,
+                nonces,
+                replaced_classes,
+                ..Default::default()
+            };
+            for (class_hash, compiled_class_hash) in classes {
+                match compiled_class_hash {
+                    Some(compiled_class_hash) => {
+                        state_diff
+                            .declared_classes
+                            .insert(class_hash, (compiled_class_hash, ContractClass::default()));
+                    }
+                    None => {
+                        state_diff
+                            .deprecated_declared_classes
+                            .insert(class_hash, DeprecatedContractClass::default());
+                    }
+                }
+            }
+            state_diff
+        })
+}
+
+// A strategy for an index map with strictly increasing keys.
+fn sorted_index_map_strategy<K, V>(
+    key: impl Strategy<Value = K>,
+    value: impl Strategy<Value = V>,
+) -> impl Strategy<Value = IndexMap<K, V>>
+where
+    K: Debug + Ord + Hash,
+    V: Debug,
+{
+    btree_map(key, value, 0..MAX_STATE_DIFF_MAP_SIZE).prop_map(|map| map.into_iter().collect())
+}
+
+fn address_map_strategy<V: Debug>(
+    value: impl Strategy<Value = V>,
+) -> impl Strategy<Value = IndexMap<ContractAddress, V>> {
+    sorted_index_map_strategy(contract_address_strategy(), value)
+}
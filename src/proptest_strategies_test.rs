@@ -0,0 +1,48 @@
+use indexmap::IndexMap;
+use proptest::prelude::*;
+
+use super::state_diff_strategy;
+use crate::block_hash::state_diff_hash::calculate_state_diff_hash;
+use crate::state::ThinStateDiff;
+
+fn reversed<K: Clone + std::hash::Hash + Eq, V: Clone>(map: &IndexMap<K, V>) -> IndexMap<K, V> {
+    map.iter().rev().map(|(key, value)| (key.clone(), value.clone())).collect()
+}
+
+proptest! {
+    #[test]
+    fn declared_and_deprecated_declared_classes_are_disjoint(state_diff in state_diff_strategy()) {
+        for class_hash in state_diff.declared_classes.keys() {
+            prop_assert!(!state_diff.deprecated_declared_classes.contains_key(class_hash));
+        }
+    }
+
+    #[test]
+    fn state_diff_hash_is_deterministic_and_order_independent(
+        state_diff in state_diff_strategy()
+    ) {
+        let (thin_state_diff, _, _) = ThinStateDiff::from_state_diff(state_diff);
+        let hash = calculate_state_diff_hash(&thin_state_diff);
+        prop_assert_eq!(&calculate_state_diff_hash(&thin_state_diff), &hash);
+
+        let reordered_state_diff = ThinStateDiff {
+            deployed_contracts: reversed(&thin_state_diff.deployed_contracts),
+            storage_diffs: thin_state_diff
+                .storage_diffs
+                .iter()
+                .rev()
+                .map(|(address, storage_entries)| (*address, reversed(storage_entries)))
+                .collect(),
+            declared_classes: reversed(&thin_state_diff.declared_classes),
+            deprecated_declared_classes: thin_state_diff
+                .deprecated_declared_classes
+                .iter()
+                .rev()
+                .copied()
+                .collect(),
+            nonces: reversed(&thin_state_diff.nonces),
+            replaced_classes: reversed(&thin_state_diff.replaced_classes),
+        };
+        prop_assert_eq!(calculate_state_diff_hash(&reordered_state_diff), hash);
+    }
+}
@@ -9,15 +9,17 @@ use starknet_types_core::felt::Felt;
 
 use crate::core::{ClassHash, CompiledClassHash, ContractAddress, Nonce};
 use crate::data_availability::DataAvailabilityMode;
-use crate::state::EntryPoint;
+use crate::state::{EntryPoint, EntryPointType, EntryPointsByType};
 use crate::transaction::{
     AccountDeploymentData, Calldata, ContractAddressSalt, PaymasterData, Resource, ResourceBounds,
     Tip, TransactionSignature,
 };
+use crate::StarknetApiError;
 
 /// Transactions that are ready to be broadcasted to the network through RPC and are not included in
 /// a block.
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "type")]
 #[serde(deny_unknown_fields)]
 pub enum RpcTransaction {
@@ -64,6 +66,7 @@ impl RpcTransaction {
 ///
 /// [`Starknet specs`]: https://github.com/starkware-libs/starknet-specs/blob/master/api/starknet_api_openrpc.json
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "version")]
 pub enum RpcDeclareTransaction {
     #[serde(rename = "0x3")]
@@ -77,6 +80,7 @@ pub enum RpcDeclareTransaction {
 ///
 /// [`Starknet specs`]: https://github.com/starkware-libs/starknet-specs/blob/master/api/starknet_api_openrpc.json
 #[derive(Clone, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "version")]
 pub enum RpcDeployAccountTransaction {
     #[serde(rename = "0x3")]
@@ -90,6 +94,7 @@ pub enum RpcDeployAccountTransaction {
 ///
 /// [`Starknet specs`]: https://github.com/starkware-libs/starknet-specs/blob/master/api/starknet_api_openrpc.json
 #[derive(Clone, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "version")]
 pub enum RpcInvokeTransaction {
     #[serde(rename = "0x3")]
@@ -99,6 +104,7 @@ pub enum RpcInvokeTransaction {
 /// A declare transaction of a Cairo-v1 contract class that can be added to Starknet through the
 /// RPC.
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct RpcDeclareTransactionV3 {
     // TODO: Check with Shahak why we need to keep the DeclareType.
     // pub r#type: DeclareType,
@@ -117,6 +123,7 @@ pub struct RpcDeclareTransactionV3 {
 
 /// A deploy account transaction that can be added to Starknet through the RPC.
 #[derive(Clone, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct RpcDeployAccountTransactionV3 {
     pub signature: TransactionSignature,
     pub nonce: Nonce,
@@ -132,6 +139,7 @@ pub struct RpcDeployAccountTransactionV3 {
 
 /// An invoke account transaction that can be added to Starknet through the RPC.
 #[derive(Clone, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct RpcInvokeTransactionV3 {
     pub sender_address: ContractAddress,
     pub calldata: Calldata,
@@ -147,7 +155,9 @@ pub struct RpcInvokeTransactionV3 {
 
 // The contract class in SN_API state doesn't have `contract_class_version`, not following the spec.
 #[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ContractClass {
+    #[cfg_attr(feature = "schemars", schemars(with = "Vec<crate::json_schema::FeltAsHex>"))]
     pub sierra_program: Vec<Felt>,
     pub contract_class_version: String,
     pub entry_points_by_type: EntryPointByType,
@@ -155,6 +165,7 @@ pub struct ContractClass {
 }
 
 #[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct EntryPointByType {
     #[serde(rename = "CONSTRUCTOR")]
     pub constructor: Vec<EntryPoint>,
@@ -164,8 +175,31 @@ pub struct EntryPointByType {
     pub l1handler: Vec<EntryPoint>,
 }
 
+impl From<EntryPointByType> for EntryPointsByType<EntryPoint> {
+    fn from(entry_points: EntryPointByType) -> Self {
+        Self(BTreeMap::from([
+            (EntryPointType::Constructor, entry_points.constructor),
+            (EntryPointType::External, entry_points.external),
+            (EntryPointType::L1Handler, entry_points.l1handler),
+        ]))
+    }
+}
+
+impl From<EntryPointsByType<EntryPoint>> for EntryPointByType {
+    fn from(mut entry_points: EntryPointsByType<EntryPoint>) -> Self {
+        let mut take =
+            |entry_point_type| entry_points.0.remove(&entry_point_type).unwrap_or_default();
+        Self {
+            constructor: take(EntryPointType::Constructor),
+            external: take(EntryPointType::External),
+            l1handler: take(EntryPointType::L1Handler),
+        }
+    }
+}
+
 // The serialization of the struct in transaction is in capital letters, not following the spec.
 #[derive(Clone, Debug, Default, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ResourceBoundsMapping {
     pub l1_gas: ResourceBounds,
     pub l2_gas: ResourceBounds,
@@ -178,3 +212,17 @@ impl From<ResourceBoundsMapping> for crate::transaction::ResourceBoundsMapping {
         crate::transaction::ResourceBoundsMapping(map)
     }
 }
+
+impl TryFrom<crate::transaction::ResourceBoundsMapping> for ResourceBoundsMapping {
+    type Error = StarknetApiError;
+
+    /// Fails if the bounds of the L1 gas or the L2 gas are missing.
+    fn try_from(mapping: crate::transaction::ResourceBoundsMapping) -> Result<Self, Self::Error> {
+        let get = |resource| {
+            mapping.0.get(&resource).copied().ok_or_else(|| {
+                StarknetApiError::InvalidResourceMappingInitializer(format!("{:?}", mapping.0))
+            })
+        };
+        Ok(Self { l1_gas: get(Resource::L1Gas)?, l2_gas: get(Resource::L2Gas)? })
+    }
+}
@@ -1,24 +1,34 @@
 use std::sync::Arc;
 
+use assert_matches::assert_matches;
 use rstest::rstest;
 use starknet_types_core::felt::Felt;
 
-use crate::core::{ClassHash, CompiledClassHash, ContractAddress, Nonce, PatriciaKey};
+use crate::core::{
+    ClassHash, CompiledClassHash, ContractAddress, EntryPointSelector, Nonce, PatriciaKey,
+};
 use crate::rpc_transaction::{
-    ContractClass, DataAvailabilityMode, ResourceBoundsMapping, RpcDeclareTransaction,
-    RpcDeclareTransactionV3, RpcDeployAccountTransaction, RpcDeployAccountTransactionV3,
-    RpcInvokeTransaction, RpcInvokeTransactionV3, RpcTransaction,
+    ContractClass, DataAvailabilityMode, EntryPointByType, ResourceBoundsMapping,
+    RpcDeclareTransaction, RpcDeclareTransactionV3, RpcDeployAccountTransaction,
+    RpcDeployAccountTransactionV3, RpcInvokeTransaction, RpcInvokeTransactionV3, RpcTransaction,
 };
+use crate::state::{EntryPoint, EntryPointsByType, FunctionIndex};
 use crate::transaction::{
-    AccountDeploymentData, Calldata, ContractAddressSalt, PaymasterData, ResourceBounds, Tip,
-    TransactionSignature,
+    AccountDeploymentData, Calldata, ContractAddressSalt, PaymasterData, Resource, ResourceAmount,
+    ResourceBounds, ResourcePrice, Tip, TransactionSignature,
 };
-use crate::{contract_address, felt, patricia_key};
+use crate::{contract_address, felt, patricia_key, StarknetApiError};
 
 fn create_resource_bounds_for_testing() -> ResourceBoundsMapping {
     ResourceBoundsMapping {
-        l1_gas: ResourceBounds { max_amount: 100, max_price_per_unit: 12 },
-        l2_gas: ResourceBounds { max_amount: 58, max_price_per_unit: 31 },
+        l1_gas: ResourceBounds {
+            max_amount: ResourceAmount(100),
+            max_price_per_unit: ResourcePrice(12),
+        },
+        l2_gas: ResourceBounds {
+            max_amount: ResourceAmount(58),
+            max_price_per_unit: ResourcePrice(31),
+        },
     }
 }
 
@@ -78,3 +88,34 @@ fn test_rpc_transactions(#[case] tx: RpcTransaction) {
     let deserialized: RpcTransaction = serde_json::from_str(&serialized).unwrap();
     assert_eq!(tx, deserialized);
 }
+
+#[test]
+fn entry_point_by_type_conversion() {
+    let entry_point = |idx| EntryPoint {
+        function_idx: FunctionIndex(idx),
+        selector: EntryPointSelector(Felt::from(idx)),
+    };
+    let entry_point_by_type = EntryPointByType {
+        constructor: vec![entry_point(0)],
+        external: vec![entry_point(1), entry_point(2)],
+        l1handler: vec![],
+    };
+
+    let entry_points_by_type = EntryPointsByType::from(entry_point_by_type.clone());
+    assert_eq!(entry_points_by_type.external(), &[entry_point(1), entry_point(2)]);
+    assert_eq!(EntryPointByType::from(entry_points_by_type), entry_point_by_type);
+}
+
+#[test]
+fn resource_bounds_mapping_conversion() {
+    let rpc_mapping = create_resource_bounds_for_testing();
+    let mapping = crate::transaction::ResourceBoundsMapping::from(rpc_mapping.clone());
+    assert_eq!(ResourceBoundsMapping::try_from(mapping.clone()).unwrap(), rpc_mapping);
+
+    let mut incomplete_mapping = mapping;
+    incomplete_mapping.0.remove(&Resource::L2Gas);
+    assert_matches!(
+        ResourceBoundsMapping::try_from(incomplete_mapping),
+        Err(StarknetApiError::InvalidResourceMappingInitializer(_))
+    );
+}
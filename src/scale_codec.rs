@@ -0,0 +1,117 @@
+//! [SCALE](https://docs.substrate.io/reference/scale-codec/) encoding of the core Starknet types.
+//!
+//! Felts are encoded as 32 big-endian bytes. Decoding rejects non-canonical felts, i.e., values
+//! that are not smaller than the field prime, and felts out of the range of the decoded type.
+#[cfg(test)]
+#[path = "scale_codec_test.rs"]
+mod scale_codec_test;
+
+use parity_scale_codec::{Decode, Encode, EncodeLike, Error, Input, Output};
+use scale_info::{Path, Type, TypeInfo};
+use starknet_types_core::felt::Felt;
+
+use crate::block::BlockHash;
+use crate::core::{
+    ClassHash, CompiledClassHash, ContractAddress, EntryPointSelector, GlobalRoot, Nonce,
+    PatriciaKey,
+};
+use crate::state::StorageKey;
+use crate::transaction::{ContractAddressSalt, EventKey, TransactionHash, TransactionVersion};
+use crate::StarknetApiError;
+
+const FELT_SIZE: usize = 32;
+
+fn decode_felt<I: Input>(input: &mut I) -> Result<Felt, Error> {
+    let bytes = <[u8; FELT_SIZE]>::decode(input)?;
+    let felt = Felt::from_bytes_be(&bytes);
+    if felt.to_bytes_be() != bytes {
+        return Err("Felt is not smaller than the field prime.".into());
+    }
+    Ok(felt)
+}
+
+macro_rules! impl_scale_codec {
+    ($type:ident, $module_path:literal, $to_felt:expr, $from_felt:expr) => {
+        impl Encode for $type {
+            fn size_hint(&self) -> usize {
+                FELT_SIZE
+            }
+
+            fn encode_to<T: Output + ?Sized>(&self, dest: &mut T) {
+                let to_felt: fn(&$type) -> Felt = $to_felt;
+                dest.write(&to_felt(self).to_bytes_be());
+            }
+        }
+
+        impl EncodeLike for $type {}
+
+        impl Decode for $type {
+            fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+                let from_felt: fn(Felt) -> Result<$type, StarknetApiError> = $from_felt;
+                from_felt(decode_felt(input)?).map_err(|_| {
+                    concat!("Felt is out of range for ", stringify!($type), ".").into()
+                })
+            }
+        }
+
+        impl TypeInfo for $type {
+            type Identity = Self;
+
+            fn type_info() -> Type {
+                Type::builder().path(Path::new(stringify!($type), $module_path)).composite(
+                    scale_info::build::Fields::unnamed()
+                        .field(|field| field.ty::<[u8; FELT_SIZE]>().type_name("Felt")),
+                )
+            }
+        }
+    };
+}
+
+impl_scale_codec!(BlockHash, "starknet_api::block", |hash| hash.0, |felt| Ok(BlockHash(felt)));
+impl_scale_codec!(ClassHash, "starknet_api::core", |hash| hash.0, |felt| Ok(ClassHash(felt)));
+impl_scale_codec!(
+    CompiledClassHash,
+    "starknet_api::core",
+    |hash| hash.0,
+    |felt| Ok(CompiledClassHash(felt))
+);
+impl_scale_codec!(
+    EntryPointSelector,
+    "starknet_api::core",
+    |selector| selector.0,
+    |felt| Ok(EntryPointSelector(felt))
+);
+impl_scale_codec!(GlobalRoot, "starknet_api::core", |root| root.0, |felt| Ok(GlobalRoot(felt)));
+impl_scale_codec!(Nonce, "starknet_api::core", |nonce| nonce.0, |felt| Ok(Nonce(felt)));
+impl_scale_codec!(
+    ContractAddressSalt,
+    "starknet_api::transaction",
+    |salt| salt.0,
+    |felt| Ok(ContractAddressSalt(felt))
+);
+impl_scale_codec!(EventKey, "starknet_api::transaction", |key| key.0, |felt| Ok(EventKey(felt)));
+impl_scale_codec!(
+    TransactionHash,
+    "starknet_api::transaction",
+    |hash| hash.0,
+    |felt| Ok(TransactionHash(felt))
+);
+impl_scale_codec!(
+    TransactionVersion,
+    "starknet_api::transaction",
+    |version| version.0,
+    |felt| Ok(TransactionVersion(felt))
+);
+impl_scale_codec!(PatriciaKey, "starknet_api::core", |key| *key.key(), PatriciaKey::try_from);
+impl_scale_codec!(
+    ContractAddress,
+    "starknet_api::core",
+    |address| *address.0.key(),
+    ContractAddress::try_from
+);
+impl_scale_codec!(
+    StorageKey,
+    "starknet_api::state",
+    |key| *key.0.key(),
+    |felt| Ok(StorageKey(PatriciaKey::try_from(felt)?))
+);
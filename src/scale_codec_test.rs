@@ -0,0 +1,63 @@
+use std::fmt::Debug;
+
+use parity_scale_codec::{Decode, Encode};
+use rstest::rstest;
+use starknet_types_core::felt::Felt;
+
+use crate::block::BlockHash;
+use crate::core::{
+    ClassHash, CompiledClassHash, ContractAddress, EntryPointSelector, GlobalRoot, Nonce,
+    PatriciaKey, PATRICIA_KEY_UPPER_BOUND,
+};
+use crate::state::StorageKey;
+use crate::transaction::{ContractAddressSalt, EventKey, TransactionHash, TransactionVersion};
+use crate::{contract_address, felt, patricia_key};
+
+fn assert_round_trip<T: Encode + Decode + Debug + PartialEq>(value: T, felt: Felt) {
+    let encoded = value.encode();
+    assert_eq!(encoded, felt.to_bytes_be().to_vec());
+    assert_eq!(T::decode(&mut encoded.as_slice()).unwrap(), value);
+}
+
+#[rstest]
+#[case::zero(Felt::ZERO)]
+#[case::small(felt!("0x1234"))]
+#[case::max_patricia_key(Felt::from_hex_unchecked(PATRICIA_KEY_UPPER_BOUND) - Felt::ONE)]
+fn scale_round_trip(#[case] felt: Felt) {
+    assert_round_trip(BlockHash(felt), felt);
+    assert_round_trip(ClassHash(felt), felt);
+    assert_round_trip(CompiledClassHash(felt), felt);
+    assert_round_trip(EntryPointSelector(felt), felt);
+    assert_round_trip(GlobalRoot(felt), felt);
+    assert_round_trip(Nonce(felt), felt);
+    assert_round_trip(ContractAddressSalt(felt), felt);
+    assert_round_trip(EventKey(felt), felt);
+    assert_round_trip(TransactionHash(felt), felt);
+    assert_round_trip(TransactionVersion(felt), felt);
+    assert_round_trip(PatriciaKey::try_from(felt).unwrap(), felt);
+    assert_round_trip(ContractAddress::try_from(felt).unwrap(), felt);
+    assert_round_trip(StorageKey(PatriciaKey::try_from(felt).unwrap()), felt);
+}
+
+#[test]
+fn scale_decode_out_of_range() {
+    let out_of_patricia_key_range =
+        Felt::from_hex_unchecked(PATRICIA_KEY_UPPER_BOUND).to_bytes_be();
+    assert!(ClassHash::decode(&mut out_of_patricia_key_range.as_slice()).is_ok());
+    assert!(PatriciaKey::decode(&mut out_of_patricia_key_range.as_slice()).is_err());
+    assert!(ContractAddress::decode(&mut out_of_patricia_key_range.as_slice()).is_err());
+    assert!(StorageKey::decode(&mut out_of_patricia_key_range.as_slice()).is_err());
+
+    let non_canonical_felt = [0xff_u8; 32];
+    assert!(Nonce::decode(&mut non_canonical_felt.as_slice()).is_err());
+
+    let short_input = [0_u8; 31];
+    assert!(BlockHash::decode(&mut short_input.as_slice()).is_err());
+}
+
+#[test]
+fn scale_encoding_in_compound_types() {
+    let pairs = vec![(contract_address!("0x1"), Nonce(felt!("0x2")))];
+    let encoded = pairs.encode();
+    assert_eq!(Vec::<(ContractAddress, Nonce)>::decode(&mut encoded.as_slice()).unwrap(), pairs);
+}
@@ -9,15 +9,29 @@ use serde::Deserializer;
 
 use crate::deprecated_contract_class::ContractClassAbiEntry;
 
+// Re-exported for the exported macros, so that they don't require `serde` at the call site.
+#[doc(hidden)]
+pub use serde;
+
 /// A [BytesAsHex](`crate::serde_utils::BytesAsHex`) prefixed with '0x'.
 pub type PrefixedBytesAsHex<const N: usize> = BytesAsHex<N, true>;
 
 /// A byte array that serializes as a hex string.
 ///
 /// The `PREFIXED` generic type symbolize whether a string representation of the hex value should be
-/// prefixed by `0x` or not.
+/// prefixed by `0x` or not. In human readable formats, the hex string is big-endian, with its
+/// leading zeros stripped on serialization and padded back on deserialization (see
+/// [bytes_from_hex_str] and [hex_str_from_bytes]). Other formats serialize the bytes as is.
+///
+/// ```
+/// use starknet_api::serde_utils::PrefixedBytesAsHex;
+///
+/// let bytes: PrefixedBytesAsHex<4> = serde_json::from_str("\"0xda2b\"").unwrap();
+/// assert_eq!(bytes.0, [0x00, 0x00, 0xda, 0x2b]);
+/// assert_eq!(serde_json::to_string(&bytes).unwrap(), "\"0xda2b\"");
+/// ```
 #[derive(Debug, Eq, PartialEq)]
-pub struct BytesAsHex<const N: usize, const PREFIXED: bool>(pub(crate) [u8; N]);
+pub struct BytesAsHex<const N: usize, const PREFIXED: bool>(pub [u8; N]);
 
 impl<'de, const N: usize, const PREFIXED: bool> Deserialize<'de> for BytesAsHex<N, PREFIXED> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
@@ -29,7 +43,7 @@ impl<'de, const N: usize, const PREFIXED: bool> Deserialize<'de> for BytesAsHex<
             type Value = BytesAsHex<N, PREFIXED>;
 
             fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                formatter.write_str("a byte array")
+                write!(formatter, "a byte array of length {N}")
             }
 
             fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
@@ -37,10 +51,14 @@ impl<'de, const N: usize, const PREFIXED: bool> Deserialize<'de> for BytesAsHex<
                 A: serde::de::SeqAccess<'de>,
             {
                 let mut res = [0u8; N];
-                let mut i = 0;
-                while let Some(value) = seq.next_element()? {
-                    res[i] = value;
-                    i += 1;
+                for (i, byte) in res.iter_mut().enumerate() {
+                    *byte = seq
+                        .next_element()?
+                        .ok_or_else(|| serde::de::Error::invalid_length(i, &self))?;
+                }
+                // Reject extra bytes rather than truncating the value.
+                if seq.next_element::<u8>()?.is_some() {
+                    return Err(serde::de::Error::invalid_length(N + 1, &self));
                 }
                 Ok(BytesAsHex(res))
             }
@@ -75,6 +93,51 @@ impl<const N: usize, const PREFIXED: bool> Serialize for BytesAsHex<N, PREFIXED>
     }
 }
 
+/// Implements hex serialization for a newtype of an unsigned integer, through a
+/// [PrefixedBytesAsHex](`crate::serde_utils::PrefixedBytesAsHex`) of the integer's byte width:
+/// - `From<PrefixedBytesAsHex<N>> for newtype` and `From<newtype> for PrefixedBytesAsHex<N>`,
+///   packing the integer big-endian. A width that doesn't match the integer doesn't compile.
+/// - `Serialize` and `Deserialize` through these conversions.
+#[macro_export]
+macro_rules! impl_hex_serialized_int {
+    ($newtype:ident, $int_type:ty, $n_bytes:literal) => {
+        impl From<$crate::serde_utils::PrefixedBytesAsHex<$n_bytes>> for $newtype {
+            fn from(value: $crate::serde_utils::PrefixedBytesAsHex<$n_bytes>) -> Self {
+                Self(<$int_type>::from_be_bytes(value.0))
+            }
+        }
+
+        impl From<$newtype> for $crate::serde_utils::PrefixedBytesAsHex<$n_bytes> {
+            fn from(value: $newtype) -> Self {
+                $crate::serde_utils::BytesAsHex(value.0.to_be_bytes())
+            }
+        }
+
+        impl $crate::serde_utils::serde::Serialize for $newtype {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: $crate::serde_utils::serde::Serializer,
+            {
+                $crate::serde_utils::serde::Serialize::serialize(
+                    &$crate::serde_utils::PrefixedBytesAsHex::<$n_bytes>::from(*self),
+                    serializer,
+                )
+            }
+        }
+
+        impl<'de> $crate::serde_utils::serde::Deserialize<'de> for $newtype {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: $crate::serde_utils::serde::Deserializer<'de>,
+            {
+                <$crate::serde_utils::PrefixedBytesAsHex<$n_bytes> as
+                    $crate::serde_utils::serde::Deserialize<'de>>::deserialize(deserializer)
+                    .map(Self::from)
+            }
+        }
+    };
+}
+
 /// The error type returned by the inner deserialization.
 // If you need `eq`, add `impl Eq for InnerDeserializationError {}` and read warning below.
 //
@@ -98,6 +161,9 @@ pub enum InnerDeserializationError {
 }
 
 /// Deserializes a Hex decoded as string to a byte array.
+///
+/// The string is big-endian and may be shorter than `2 * N` hex digits, in which case it's padded
+/// with leading zeros; a longer string is rejected. If `PREFIXED`, the string must start with `0x`.
 pub fn bytes_from_hex_str<const N: usize, const PREFIXED: bool>(
     hex_str: &str,
 ) -> Result<[u8; N], InnerDeserializationError> {
@@ -126,7 +192,9 @@ pub fn bytes_from_hex_str<const N: usize, const PREFIXED: bool>(
     Ok(hex::decode(padded_str)?.try_into().expect("Unexpected length of deserialized hex bytes."))
 }
 
-/// Encodes a byte array to a string.
+/// Encodes a byte array to a big-endian hex string, prefixed with `0x` if `PREFIXED`.
+///
+/// Leading zeros are stripped, so a zero array is encoded as `0`.
 pub fn hex_str_from_bytes<const N: usize, const PREFIXED: bool>(bytes: [u8; N]) -> String {
     let hex_str = hex::encode(bytes);
     let mut hex_str = hex_str.trim_start_matches('0');
@@ -149,3 +217,335 @@ where
         Err(_) => Ok(None),
     }
 }
+
+/// Serializes a [ContractAddress](`crate::core::ContractAddress`) as a decimal string, e.g., for
+/// interoperability with databases that store addresses as numbers.
+///
+/// Usage: `#[serde(with = "crate::serde_utils::contract_address_as_decimal")]`.
+pub mod contract_address_as_decimal {
+    use primitive_types::U256;
+    use serde::de::Error as DeserializationError;
+    use serde::{Deserialize, Deserializer, Serializer};
+    use starknet_types_core::felt::Felt;
+
+    use crate::core::{ContractAddress, PATRICIA_KEY_UPPER_BOUND};
+    use crate::StarknetApiError;
+
+    // log2 of the size of the contract address domain.
+    const CONTRACT_ADDRESS_DOMAIN_BITS: usize = 251;
+
+    pub fn serialize<S>(
+        contract_address: &ContractAddress,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        // The Display of a felt is its decimal representation.
+        serializer.serialize_str(&contract_address.0.key().to_string())
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<ContractAddress, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let decimal_str = String::deserialize(deserializer)?;
+        let value = U256::from_dec_str(&decimal_str).map_err(|_| {
+            DeserializationError::custom(format!("Invalid decimal string: {decimal_str}."))
+        })?;
+        // Values above the field prime would be reduced when converted to a felt, so the range is
+        // checked before the conversion.
+        if value.bits() > CONTRACT_ADDRESS_DOMAIN_BITS {
+            return Err(DeserializationError::custom(StarknetApiError::OutOfRange {
+                string: format!("[0x0, {PATRICIA_KEY_UPPER_BOUND})"),
+            }));
+        }
+        let mut bytes = [0_u8; 32];
+        value.to_big_endian(&mut bytes);
+        ContractAddress::try_from(Felt::from_bytes_be(&bytes)).map_err(DeserializationError::custom)
+    }
+}
+
+/// Serializes a [ChainId](`crate::core::ChainId`) in its hex form, as it's referenced by L1
+/// contracts, instead of the default string form.
+///
+/// Usage: `#[serde(with = "crate::serde_utils::chain_id_as_felt_hex")]`.
+pub mod chain_id_as_felt_hex {
+    use serde::de::Error as DeserializationError;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use crate::core::ChainId;
+
+    pub fn serialize<S>(chain_id: &ChainId, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&chain_id.as_hex())
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<ChainId, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let hex_str = String::deserialize(deserializer)?;
+        ChainId::from_hex(&hex_str).map_err(DeserializationError::custom)
+    }
+}
+
+/// Deserializes a number that may be given as a JSON number, a decimal string or a `0x`-prefixed
+/// hex string, as different feeders represent [Fee](`crate::transaction::Fee`),
+/// [Tip](`crate::transaction::Tip`), [GasPrice](`crate::block::GasPrice`) and
+/// [Nonce](`crate::core::Nonce`) differently. Serialization is the default one of the type.
+///
+/// Usage: `#[serde(with = "crate::serde_utils::flexible_number")]`.
+pub mod flexible_number {
+    use std::fmt;
+
+    use primitive_types::U256;
+    use serde::de::{Error as DeserializationError, Visitor};
+    use serde::{Deserializer, Serialize, Serializer};
+    use starknet_types_core::felt::Felt;
+
+    use crate::block::GasPrice;
+    use crate::core::Nonce;
+    use crate::transaction::{Fee, Tip};
+
+    /// A type that can be deserialized by [flexible_number](`crate::serde_utils::flexible_number`).
+    pub trait FlexibleNumber: Sized {
+        /// Converts the value to the type, or returns None if it's out of range.
+        fn try_from_u256(value: U256) -> Option<Self>;
+    }
+
+    impl FlexibleNumber for Fee {
+        fn try_from_u256(value: U256) -> Option<Self> {
+            (value.bits() <= 128).then(|| Fee(value.as_u128()))
+        }
+    }
+
+    impl FlexibleNumber for GasPrice {
+        fn try_from_u256(value: U256) -> Option<Self> {
+            (value.bits() <= 128).then(|| GasPrice(value.as_u128()))
+        }
+    }
+
+    impl FlexibleNumber for Tip {
+        fn try_from_u256(value: U256) -> Option<Self> {
+            (value.bits() <= 64).then(|| Tip(value.as_u64()))
+        }
+    }
+
+    impl FlexibleNumber for Nonce {
+        fn try_from_u256(value: U256) -> Option<Self> {
+            let mut bytes = [0_u8; 32];
+            value.to_big_endian(&mut bytes);
+            // Values above the field prime are reduced by the conversion.
+            let felt = Felt::from_bytes_be(&bytes);
+            (felt.to_bytes_be() == bytes).then_some(Nonce(felt))
+        }
+    }
+
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Serialize,
+        S: Serializer,
+    {
+        value.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: FlexibleNumber,
+        D: Deserializer<'de>,
+    {
+        let value = deserializer.deserialize_any(FlexibleNumberVisitor)?;
+        T::try_from_u256(value).ok_or_else(|| {
+            DeserializationError::custom(format!(
+                "Number {value:#x} is out of range for {}.",
+                std::any::type_name::<T>()
+            ))
+        })
+    }
+
+    struct FlexibleNumberVisitor;
+
+    impl<'de> Visitor<'de> for FlexibleNumberVisitor {
+        type Value = U256;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("a number, a decimal string or a 0x-prefixed hex string")
+        }
+
+        fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
+        where
+            E: DeserializationError,
+        {
+            Ok(U256::from(value))
+        }
+
+        fn visit_u128<E>(self, value: u128) -> Result<Self::Value, E>
+        where
+            E: DeserializationError,
+        {
+            Ok(U256::from(value))
+        }
+
+        fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E>
+        where
+            E: DeserializationError,
+        {
+            u64::try_from(value)
+                .map(U256::from)
+                .map_err(|_| E::custom(format!("Expected a non-negative number; got {value}.")))
+        }
+
+        fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+        where
+            E: DeserializationError,
+        {
+            let parsed = match value.strip_prefix("0x").or_else(|| value.strip_prefix("0X")) {
+                Some(hex_str) if !hex_str.is_empty() => U256::from_str_radix(hex_str, 16).ok(),
+                Some(_) => None,
+                None => U256::from_dec_str(value).ok(),
+            };
+            parsed.ok_or_else(|| E::custom(format!("Invalid number string: {value:?}.")))
+        }
+    }
+}
+
+/// Serializes a [Transaction](`crate::transaction::Transaction`) as a single flat object, tagged
+/// by its `type` and `version`, for all transaction types alike. Deploy and L1 handler
+/// transactions carry their version as a field, and the version of the other transactions is
+/// the tag of their variant.
+///
+/// Usage: `#[serde(with = "crate::serde_utils::transaction_with_type")]`.
+pub mod transaction_with_type {
+    use std::borrow::Cow;
+
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::transaction::{
+        DeclareTransaction, DeclareTransactionV0V1, DeclareTransactionV2, DeclareTransactionV3,
+        DeployAccountTransaction, DeployAccountTransactionV1, DeployAccountTransactionV3,
+        DeployTransaction, InvokeTransaction, InvokeTransactionV0, InvokeTransactionV1,
+        InvokeTransactionV3, L1HandlerTransaction, Transaction,
+    };
+
+    // The tags match the serialization of TransactionType.
+    #[derive(Deserialize, Serialize)]
+    #[serde(tag = "type")]
+    enum TypedTransaction<'a> {
+        #[serde(rename = "DECLARE")]
+        Declare(TypedDeclareTransaction<'a>),
+        #[serde(rename = "DEPLOY")]
+        Deploy(Cow<'a, DeployTransaction>),
+        #[serde(rename = "DEPLOY_ACCOUNT")]
+        DeployAccount(TypedDeployAccountTransaction<'a>),
+        #[serde(rename = "INVOKE")]
+        Invoke(TypedInvokeTransaction<'a>),
+        #[serde(rename = "L1_HANDLER")]
+        L1Handler(Cow<'a, L1HandlerTransaction>),
+    }
+
+    #[derive(Deserialize, Serialize)]
+    #[serde(tag = "version")]
+    enum TypedDeclareTransaction<'a> {
+        #[serde(rename = "0x0")]
+        V0(Cow<'a, DeclareTransactionV0V1>),
+        #[serde(rename = "0x1")]
+        V1(Cow<'a, DeclareTransactionV0V1>),
+        #[serde(rename = "0x2")]
+        V2(Cow<'a, DeclareTransactionV2>),
+        #[serde(rename = "0x3")]
+        V3(Cow<'a, DeclareTransactionV3>),
+    }
+
+    #[derive(Deserialize, Serialize)]
+    #[serde(tag = "version")]
+    enum TypedDeployAccountTransaction<'a> {
+        #[serde(rename = "0x1")]
+        V1(Cow<'a, DeployAccountTransactionV1>),
+        #[serde(rename = "0x3")]
+        V3(Cow<'a, DeployAccountTransactionV3>),
+    }
+
+    #[derive(Deserialize, Serialize)]
+    #[serde(tag = "version")]
+    enum TypedInvokeTransaction<'a> {
+        #[serde(rename = "0x0")]
+        V0(Cow<'a, InvokeTransactionV0>),
+        #[serde(rename = "0x1")]
+        V1(Cow<'a, InvokeTransactionV1>),
+        #[serde(rename = "0x3")]
+        V3(Cow<'a, InvokeTransactionV3>),
+    }
+
+    impl<'a> From<&'a Transaction> for TypedTransaction<'a> {
+        fn from(transaction: &'a Transaction) -> Self {
+            match transaction {
+                Transaction::Declare(tx) => TypedTransaction::Declare(match tx {
+                    DeclareTransaction::V0(tx) => TypedDeclareTransaction::V0(Cow::Borrowed(tx)),
+                    DeclareTransaction::V1(tx) => TypedDeclareTransaction::V1(Cow::Borrowed(tx)),
+                    DeclareTransaction::V2(tx) => TypedDeclareTransaction::V2(Cow::Borrowed(tx)),
+                    DeclareTransaction::V3(tx) => TypedDeclareTransaction::V3(Cow::Borrowed(tx)),
+                }),
+                Transaction::Deploy(tx) => TypedTransaction::Deploy(Cow::Borrowed(tx)),
+                Transaction::DeployAccount(tx) => TypedTransaction::DeployAccount(match tx {
+                    DeployAccountTransaction::V1(tx) => {
+                        TypedDeployAccountTransaction::V1(Cow::Borrowed(tx))
+                    }
+                    DeployAccountTransaction::V3(tx) => {
+                        TypedDeployAccountTransaction::V3(Cow::Borrowed(tx))
+                    }
+                }),
+                Transaction::Invoke(tx) => TypedTransaction::Invoke(match tx {
+                    InvokeTransaction::V0(tx) => TypedInvokeTransaction::V0(Cow::Borrowed(tx)),
+                    InvokeTransaction::V1(tx) => TypedInvokeTransaction::V1(Cow::Borrowed(tx)),
+                    InvokeTransaction::V3(tx) => TypedInvokeTransaction::V3(Cow::Borrowed(tx)),
+                }),
+                Transaction::L1Handler(tx) => TypedTransaction::L1Handler(Cow::Borrowed(tx)),
+            }
+        }
+    }
+
+    impl From<TypedTransaction<'_>> for Transaction {
+        fn from(transaction: TypedTransaction<'_>) -> Self {
+            match transaction {
+                TypedTransaction::Declare(tx) => Transaction::Declare(match tx {
+                    TypedDeclareTransaction::V0(tx) => DeclareTransaction::V0(tx.into_owned()),
+                    TypedDeclareTransaction::V1(tx) => DeclareTransaction::V1(tx.into_owned()),
+                    TypedDeclareTransaction::V2(tx) => DeclareTransaction::V2(tx.into_owned()),
+                    TypedDeclareTransaction::V3(tx) => DeclareTransaction::V3(tx.into_owned()),
+                }),
+                TypedTransaction::Deploy(tx) => Transaction::Deploy(tx.into_owned()),
+                TypedTransaction::DeployAccount(tx) => Transaction::DeployAccount(match tx {
+                    TypedDeployAccountTransaction::V1(tx) => {
+                        DeployAccountTransaction::V1(tx.into_owned())
+                    }
+                    TypedDeployAccountTransaction::V3(tx) => {
+                        DeployAccountTransaction::V3(tx.into_owned())
+                    }
+                }),
+                TypedTransaction::Invoke(tx) => Transaction::Invoke(match tx {
+                    TypedInvokeTransaction::V0(tx) => InvokeTransaction::V0(tx.into_owned()),
+                    TypedInvokeTransaction::V1(tx) => InvokeTransaction::V1(tx.into_owned()),
+                    TypedInvokeTransaction::V3(tx) => InvokeTransaction::V3(tx.into_owned()),
+                }),
+                TypedTransaction::L1Handler(tx) => Transaction::L1Handler(tx.into_owned()),
+            }
+        }
+    }
+
+    pub fn serialize<S>(transaction: &Transaction, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        TypedTransaction::from(transaction).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Transaction, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(TypedTransaction::deserialize(deserializer)?.into())
+    }
+}
@@ -1,13 +1,26 @@
 use assert_matches::assert_matches;
-use serde::Deserialize;
+use rstest::rstest;
+use serde::de::value::SeqDeserializer;
+use serde::de::Visitor;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::json;
 
+use crate::block::GasPrice;
+use crate::core::{ChainId, ClassHash, ContractAddress, Nonce, PatriciaKey};
 use crate::deprecated_contract_class::{
     ConstructorType, ContractClassAbiEntry, FunctionAbiEntry, TypedParameter,
 };
 use crate::serde_utils::{
-    bytes_from_hex_str, deserialize_optional_contract_class_abi_entry_vector, hex_str_from_bytes,
-    BytesAsHex, InnerDeserializationError,
+    bytes_from_hex_str, chain_id_as_felt_hex, contract_address_as_decimal,
+    deserialize_optional_contract_class_abi_entry_vector, hex_str_from_bytes, BytesAsHex,
+    InnerDeserializationError,
 };
+use crate::transaction::{
+    Calldata, DeclareTransaction, DeclareTransactionV2, DeployAccountTransaction,
+    DeployAccountTransactionV1, DeployTransaction, Fee, InvokeTransaction, InvokeTransactionV1,
+    L1HandlerTransaction, Tip, Transaction, TransactionVersion,
+};
+use crate::{calldata, class_hash, contract_address, felt, patricia_key};
 
 #[test]
 fn hex_str_from_bytes_scenarios() {
@@ -120,6 +133,64 @@ struct DummyContractClass {
     pub abi: Option<Vec<ContractClassAbiEntry>>,
 }
 
+#[test]
+fn hex_serialized_int_round_trip() {
+    let fee = Fee(u128::MAX);
+    let tip = Tip(0xda2b);
+    let gas_price = GasPrice(0);
+
+    assert_eq!(serde_json::to_value(fee).unwrap(), json!(format!("{:#x}", u128::MAX)));
+    assert_eq!(serde_json::to_value(tip).unwrap(), json!("0xda2b"));
+    assert_eq!(serde_json::to_value(gas_price).unwrap(), json!("0x0"));
+
+    assert_eq!(serde_json::from_value::<Fee>(serde_json::to_value(fee).unwrap()).unwrap(), fee);
+    assert_eq!(serde_json::from_value::<Tip>(serde_json::to_value(tip).unwrap()).unwrap(), tip);
+    assert_eq!(
+        serde_json::from_value::<GasPrice>(serde_json::to_value(gas_price).unwrap()).unwrap(),
+        gas_price
+    );
+}
+
+// A deserializer of a byte sequence in a non human readable format.
+struct CompactBytesDeserializer(Vec<u8>);
+
+impl<'de> Deserializer<'de> for CompactBytesDeserializer {
+    type Error = serde::de::value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_seq(SeqDeserializer::new(self.0.into_iter()))
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf option
+        unit unit_struct newtype_struct seq tuple tuple_struct map struct enum identifier
+        ignored_any
+    }
+}
+
+#[test]
+fn hex_serialized_int_oversized() {
+    // 17 bytes don't fit in a fee.
+    let err = serde_json::from_value::<Fee>(json!(format!("0x{}", "ff".repeat(17)))).unwrap_err();
+    assert!(err.to_string().contains("expected #bytes: 16"), "{err}");
+    assert_matches!(
+        bytes_from_hex_str::<16, true>(&format!("0x1{}", "0".repeat(32))),
+        Err(InnerDeserializationError::BadInput { expected_byte_count: 16, .. })
+    );
+    // The width is checked by digits, so leading zeros count as well.
+    assert!(serde_json::from_value::<GasPrice>(json!(format!("0x{}", "0".repeat(33)))).is_err());
+
+    // Non human readable formats check the number of bytes as well.
+    let tip_bytes = vec![0, 0, 0, 0, 0, 0, 0, 5];
+    assert_eq!(Tip::deserialize(CompactBytesDeserializer(tip_bytes)), Ok(Tip(5)));
+    assert!(Tip::deserialize(CompactBytesDeserializer(vec![0; 9])).is_err());
+    assert!(Tip::deserialize(CompactBytesDeserializer(vec![0; 7])).is_err());
+}
+
 #[test]
 fn deserialize_valid_optional_contract_class_abi_entry_vector() {
     let json = r#"
@@ -180,3 +251,200 @@ fn deserialize_optional_contract_class_abi_entry_vector_none() {
     let res: DummyContractClass = serde_json::from_str(json).unwrap();
     assert_eq!(res, DummyContractClass { abi: None });
 }
+
+#[derive(Deserialize, Serialize, PartialEq, Eq, Debug)]
+struct DummyDecimalAddress {
+    #[serde(with = "contract_address_as_decimal")]
+    pub address: ContractAddress,
+}
+
+#[test]
+fn contract_address_as_decimal_small() {
+    let dummy = DummyDecimalAddress { address: contract_address!("0x1234") };
+    let serialized = serde_json::to_string(&dummy).unwrap();
+    assert_eq!(serialized, r#"{"address":"4660"}"#);
+    assert_eq!(serde_json::from_str::<DummyDecimalAddress>(&serialized).unwrap(), dummy);
+}
+
+#[test]
+fn contract_address_as_decimal_near_max() {
+    // 2**251 - 1.
+    let dummy = DummyDecimalAddress {
+        address: contract_address!(
+            "0x7ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff"
+        ),
+    };
+    let decimal_address =
+        "3618502788666131106986593281521497120414687020801267626233049500247285301247";
+    let serialized = serde_json::to_string(&dummy).unwrap();
+    assert_eq!(serialized, format!(r#"{{"address":"{decimal_address}"}}"#));
+    assert_eq!(serde_json::from_str::<DummyDecimalAddress>(&serialized).unwrap(), dummy);
+}
+
+#[test]
+fn contract_address_as_decimal_out_of_range() {
+    // 2**251.
+    let decimal_address =
+        "3618502788666131106986593281521497120414687020801267626233049500247285301248";
+    let json = format!(r#"{{"address":"{decimal_address}"}}"#);
+    assert!(serde_json::from_str::<DummyDecimalAddress>(&json).is_err());
+
+    // The field prime + 1; would be reduced to 1 if converted to a felt without a range check.
+    let decimal_address =
+        "3618502788666131213697322783095070105623107215331596699973092056135872020482";
+    let json = format!(r#"{{"address":"{decimal_address}"}}"#);
+    assert!(serde_json::from_str::<DummyDecimalAddress>(&json).is_err());
+
+    // Not a decimal string.
+    let json = r#"{"address":"0x1234"}"#;
+    assert!(serde_json::from_str::<DummyDecimalAddress>(json).is_err());
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Eq, Debug)]
+struct DummyHexChainId {
+    #[serde(with = "chain_id_as_felt_hex")]
+    pub chain_id: ChainId,
+}
+
+#[test]
+fn chain_id_as_felt_hex_round_trip() {
+    let dummy = DummyHexChainId { chain_id: ChainId::Mainnet };
+    let serialized = serde_json::to_string(&dummy).unwrap();
+    assert_eq!(serialized, r#"{"chain_id":"0x534e5f4d41494e"}"#);
+    assert_eq!(serde_json::from_str::<DummyHexChainId>(&serialized).unwrap(), dummy);
+
+    let dummy = DummyHexChainId { chain_id: ChainId::Other("MY_CHAIN".to_owned()) };
+    let serialized = serde_json::to_string(&dummy).unwrap();
+    assert_eq!(serde_json::from_str::<DummyHexChainId>(&serialized).unwrap(), dummy);
+
+    // The default serialization is unchanged.
+    assert_eq!(serde_json::to_string(&ChainId::Mainnet).unwrap(), r#""SN_MAIN""#);
+}
+
+#[test]
+fn chain_id_as_felt_hex_invalid() {
+    assert!(serde_json::from_str::<DummyHexChainId>(r#"{"chain_id":"SN_MAIN"}"#).is_err());
+    assert!(serde_json::from_str::<DummyHexChainId>(r#"{"chain_id":"0xzz"}"#).is_err());
+    assert!(serde_json::from_str::<DummyHexChainId>(r#"{"chain_id":"0xff"}"#).is_err());
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
+struct FlexibleNumbers {
+    #[serde(with = "crate::serde_utils::flexible_number")]
+    fee: Fee,
+    #[serde(with = "crate::serde_utils::flexible_number")]
+    tip: Tip,
+    #[serde(with = "crate::serde_utils::flexible_number")]
+    gas_price: GasPrice,
+    #[serde(with = "crate::serde_utils::flexible_number")]
+    nonce: Nonce,
+}
+
+fn flexible_numbers() -> FlexibleNumbers {
+    FlexibleNumbers {
+        fee: Fee(1000),
+        tip: Tip(5),
+        gas_price: GasPrice(7),
+        nonce: Nonce(felt!("0x3")),
+    }
+}
+
+#[rstest]
+#[case::numbers(json!({ "fee": 1000, "tip": 5, "gas_price": 7, "nonce": 3 }))]
+#[case::decimal_strings(json!({ "fee": "1000", "tip": "5", "gas_price": "7", "nonce": "3" }))]
+#[case::hex_strings(json!({ "fee": "0x3e8", "tip": "0x5", "gas_price": "0x7", "nonce": "0x3" }))]
+#[case::mixed(json!({ "fee": "0x3e8", "tip": 5, "gas_price": "7", "nonce": "0x3" }))]
+fn flexible_number_input_forms(#[case] input: serde_json::Value) {
+    assert_eq!(serde_json::from_value::<FlexibleNumbers>(input).unwrap(), flexible_numbers());
+}
+
+#[test]
+fn flexible_number_serializes_as_default() {
+    let numbers = flexible_numbers();
+    let serialized = serde_json::to_value(&numbers).unwrap();
+    assert_eq!(serialized["fee"], serde_json::to_value(Fee(1000)).unwrap());
+    assert_eq!(serialized["tip"], serde_json::to_value(Tip(5)).unwrap());
+    assert_eq!(serde_json::from_value::<FlexibleNumbers>(serialized).unwrap(), numbers);
+}
+
+#[rstest]
+#[case::tip_out_of_range(json!({
+    "fee": 1, "tip": "0x10000000000000000", "gas_price": 1, "nonce": 1
+}))]
+#[case::fee_out_of_range(json!({
+    "fee": "0x100000000000000000000000000000000", "tip": 1, "gas_price": 1, "nonce": 1
+}))]
+#[case::nonce_out_of_range(json!({
+    "fee": 1,
+    "tip": 1,
+    "gas_price": 1,
+    "nonce": "0x800000000000011000000000000000000000000000000000000000000000001"
+}))]
+#[case::negative(json!({ "fee": 1, "tip": -1, "gas_price": 1, "nonce": 1 }))]
+#[case::invalid_string(json!({ "fee": "ten", "tip": 1, "gas_price": 1, "nonce": 1 }))]
+#[case::empty_hex(json!({ "fee": "0x", "tip": 1, "gas_price": 1, "nonce": 1 }))]
+fn flexible_number_invalid(#[case] input: serde_json::Value) {
+    assert!(serde_json::from_value::<FlexibleNumbers>(input).is_err());
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
+struct DummyTypedTransaction {
+    #[serde(flatten, with = "crate::serde_utils::transaction_with_type")]
+    transaction: Transaction,
+}
+
+#[rstest]
+#[case::declare(
+    Transaction::Declare(DeclareTransaction::V2(DeclareTransactionV2 {
+        class_hash: class_hash!("0x1"),
+        ..Default::default()
+    })),
+    "DECLARE",
+    "0x2"
+)]
+#[case::deploy(
+    Transaction::Deploy(DeployTransaction {
+        version: TransactionVersion::ONE,
+        class_hash: class_hash!("0x1"),
+        ..Default::default()
+    }),
+    "DEPLOY",
+    "0x1"
+)]
+#[case::deploy_account(
+    Transaction::DeployAccount(DeployAccountTransaction::V1(DeployAccountTransactionV1 {
+        nonce: Nonce(felt!("0x2")),
+        ..Default::default()
+    })),
+    "DEPLOY_ACCOUNT",
+    "0x1"
+)]
+#[case::invoke(
+    Transaction::Invoke(InvokeTransaction::V1(InvokeTransactionV1 {
+        calldata: calldata![felt!("0x3")],
+        ..Default::default()
+    })),
+    "INVOKE",
+    "0x1"
+)]
+#[case::l1_handler(
+    Transaction::L1Handler(L1HandlerTransaction {
+        contract_address: contract_address!("0x4"),
+        ..Default::default()
+    }),
+    "L1_HANDLER",
+    "0x0"
+)]
+fn transaction_with_type_round_trip(
+    #[case] transaction: Transaction,
+    #[case] expected_type: &str,
+    #[case] expected_version: &str,
+) {
+    let dummy = DummyTypedTransaction { transaction };
+    let serialized = serde_json::to_value(&dummy).unwrap();
+    assert_eq!(serialized["type"], expected_type);
+    let transaction_type = dummy.transaction.transaction_type();
+    assert_eq!(serialized["type"], serde_json::to_value(transaction_type).unwrap());
+    assert_eq!(serialized["version"], expected_version);
+    assert_eq!(serde_json::from_value::<DummyTypedTransaction>(serialized).unwrap(), dummy);
+}
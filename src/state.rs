@@ -2,21 +2,26 @@
 #[path = "state_test.rs"]
 mod state_test;
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt::Debug;
+use std::hash::Hash;
 
+use cairo_lang_starknet_classes::abi;
 use indexmap::IndexMap;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use starknet_types_core::felt::Felt;
 
 use crate::block::{BlockHash, BlockNumber};
+use crate::block_hash::state_diff_hash::calculate_state_diff_hash;
 use crate::core::{
     ClassHash, CompiledClassHash, ContractAddress, EntryPointSelector, GlobalRoot, Nonce,
-    PatriciaKey,
+    PatriciaKey, StateDiffCommitment,
 };
 use crate::deprecated_contract_class::ContractClass as DeprecatedContractClass;
 use crate::hash::StarkHash;
-use crate::{impl_from_through_intermediate, StarknetApiError};
+#[cfg(feature = "schemars")]
+use crate::json_schema::FeltAsHex;
+use crate::{impl_from_through_intermediate, impl_inner_conversions, StarknetApiError};
 
 pub type DeclaredClasses = IndexMap<ClassHash, ContractClass>;
 pub type DeprecatedDeclaredClasses = IndexMap<ClassHash, DeprecatedContractClass>;
@@ -24,6 +29,7 @@ pub type DeprecatedDeclaredClasses = IndexMap<ClassHash, DeprecatedContractClass
 /// The differences between two states before and after a block with hash block_hash
 /// and their respective roots.
 #[derive(Debug, Default, Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct StateUpdate {
     pub block_hash: BlockHash,
     pub new_root: GlobalRoot,
@@ -31,17 +37,85 @@ pub struct StateUpdate {
     pub state_diff: StateDiff,
 }
 
+/// The kind of a declared class.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum ClassKind {
+    /// A Sierra class, i.e., a [ContractClass].
+    Sierra,
+    /// A Cairo 0 class, i.e., a [DeprecatedContractClass].
+    Deprecated,
+}
+
+/// A state update as returned by the `get_state_update` endpoint of the feeder gateway. The gateway
+/// doesn't return the definitions of the declared classes, so the state diff is thin.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Deserialize)]
+pub struct GatewayStateUpdate {
+    pub block_hash: BlockHash,
+    pub new_root: GlobalRoot,
+    pub old_root: GlobalRoot,
+    #[serde(deserialize_with = "ThinStateDiff::deserialize_gateway")]
+    pub state_diff: ThinStateDiff,
+}
+
 /// The differences between two states.
 // Invariant: Addresses are strictly increasing.
 // Invariant: Class hashes of declared_classes and deprecated_declared_classes are exclusive.
 // TODO(yair): Enforce this invariant.
 #[derive(Debug, Default, Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct StateDiff {
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::borsh_codec::serialize_index_map",
+            deserialize_with = "crate::borsh_codec::deserialize_index_map"
+        )
+    )]
     pub deployed_contracts: IndexMap<ContractAddress, ClassHash>,
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::borsh_codec::serialize_storage_diffs",
+            deserialize_with = "crate::borsh_codec::deserialize_storage_diffs"
+        )
+    )]
+    #[cfg_attr(
+        feature = "schemars",
+        schemars(with = "IndexMap<ContractAddress, IndexMap<StorageKey, FeltAsHex>>")
+    )]
     pub storage_diffs: IndexMap<ContractAddress, IndexMap<StorageKey, Felt>>,
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::borsh_codec::serialize_index_map",
+            deserialize_with = "crate::borsh_codec::deserialize_index_map"
+        )
+    )]
     pub declared_classes: IndexMap<ClassHash, (CompiledClassHash, ContractClass)>,
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::borsh_codec::serialize_index_map",
+            deserialize_with = "crate::borsh_codec::deserialize_index_map"
+        )
+    )]
     pub deprecated_declared_classes: IndexMap<ClassHash, DeprecatedContractClass>,
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::borsh_codec::serialize_index_map",
+            deserialize_with = "crate::borsh_codec::deserialize_index_map"
+        )
+    )]
     pub nonces: IndexMap<ContractAddress, Nonce>,
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::borsh_codec::serialize_index_map",
+            deserialize_with = "crate::borsh_codec::deserialize_index_map"
+        )
+    )]
     pub replaced_classes: IndexMap<ContractAddress, ClassHash>,
 }
 
@@ -49,12 +123,53 @@ pub struct StateDiff {
 // The invariant is enforced as [`ThinStateDiff`] is created only from [`starknet_api`][`StateDiff`]
 // where the addresses are strictly increasing.
 #[derive(Debug, Default, Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ThinStateDiff {
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::borsh_codec::serialize_index_map",
+            deserialize_with = "crate::borsh_codec::deserialize_index_map"
+        )
+    )]
     pub deployed_contracts: IndexMap<ContractAddress, ClassHash>,
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::borsh_codec::serialize_storage_diffs",
+            deserialize_with = "crate::borsh_codec::deserialize_storage_diffs"
+        )
+    )]
+    #[cfg_attr(
+        feature = "schemars",
+        schemars(with = "IndexMap<ContractAddress, IndexMap<StorageKey, FeltAsHex>>")
+    )]
     pub storage_diffs: IndexMap<ContractAddress, IndexMap<StorageKey, Felt>>,
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::borsh_codec::serialize_index_map",
+            deserialize_with = "crate::borsh_codec::deserialize_index_map"
+        )
+    )]
     pub declared_classes: IndexMap<ClassHash, CompiledClassHash>,
     pub deprecated_declared_classes: Vec<ClassHash>,
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::borsh_codec::serialize_index_map",
+            deserialize_with = "crate::borsh_codec::deserialize_index_map"
+        )
+    )]
     pub nonces: IndexMap<ContractAddress, Nonce>,
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::borsh_codec::serialize_index_map",
+            deserialize_with = "crate::borsh_codec::deserialize_index_map"
+        )
+    )]
     pub replaced_classes: IndexMap<ContractAddress, ClassHash>,
 }
 
@@ -112,6 +227,129 @@ impl ThinStateDiff {
                 .iter()
                 .all(|(_contract_address, storage_diffs)| storage_diffs.is_empty())
     }
+
+    /// Squashes consecutive state diffs, given in ascending order, into a single cumulative diff,
+    /// where later writes win. A class replaced after being deployed within the range is squashed
+    /// into the deployment. The declared classes (and the deprecated ones) are united; as a class
+    /// is declared only once, they stay disjoint. The maps of the result are sorted by key,
+    /// keeping the invariant of strictly increasing addresses.
+    pub fn squash(diffs: &[ThinStateDiff]) -> ThinStateDiff {
+        let mut squashed = ThinStateDiff::default();
+        for diff in diffs {
+            squashed.deployed_contracts.extend(diff.deployed_contracts.iter());
+            for (address, storage_entries) in &diff.storage_diffs {
+                squashed.storage_diffs.entry(*address).or_default().extend(storage_entries.iter());
+            }
+            squashed.declared_classes.extend(diff.declared_classes.iter());
+            for class_hash in &diff.deprecated_declared_classes {
+                if !squashed.deprecated_declared_classes.contains(class_hash) {
+                    squashed.deprecated_declared_classes.push(*class_hash);
+                }
+            }
+            squashed.nonces.extend(diff.nonces.iter());
+            for (address, class_hash) in &diff.replaced_classes {
+                match squashed.deployed_contracts.get_mut(address) {
+                    Some(deployed_class_hash) => *deployed_class_hash = *class_hash,
+                    None => {
+                        squashed.replaced_classes.insert(*address, *class_hash);
+                    }
+                }
+            }
+        }
+
+        squashed.sort_keys();
+        squashed
+    }
+
+    /// Deserializes a state diff in the format of the feeder gateway, where the deployed
+    /// contracts, the declared classes, the replaced classes and the storage entries are lists of
+    /// objects rather than maps. The maps of the result are sorted by key.
+    pub fn deserialize_gateway<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        let gateway_diff = GatewayStateDiff::deserialize(deserializer)?;
+        let mut state_diff = ThinStateDiff {
+            deployed_contracts: gateway_diff
+                .deployed_contracts
+                .into_iter()
+                .map(|contract| (contract.address, contract.class_hash))
+                .collect(),
+            storage_diffs: gateway_diff
+                .storage_diffs
+                .into_iter()
+                .map(|(address, entries)| {
+                    (address, entries.into_iter().map(|entry| (entry.key, entry.value)).collect())
+                })
+                .collect(),
+            declared_classes: gateway_diff
+                .declared_classes
+                .into_iter()
+                .map(|class| (class.class_hash, class.compiled_class_hash))
+                .collect(),
+            deprecated_declared_classes: gateway_diff.old_declared_contracts,
+            nonces: gateway_diff.nonces,
+            replaced_classes: gateway_diff
+                .replaced_classes
+                .into_iter()
+                .map(|contract| (contract.address, contract.class_hash))
+                .collect(),
+        };
+        state_diff.sort_keys();
+        Ok(state_diff)
+    }
+
+    // Sorts the maps by key, to keep the invariant of strictly increasing addresses.
+    fn sort_keys(&mut self) {
+        self.deployed_contracts.sort_unstable_keys();
+        self.storage_diffs.sort_unstable_keys();
+        for storage_entries in self.storage_diffs.values_mut() {
+            storage_entries.sort_unstable_keys();
+        }
+        self.declared_classes.sort_unstable_keys();
+        self.nonces.sort_unstable_keys();
+        self.replaced_classes.sort_unstable_keys();
+    }
+
+    /// Returns true if every entry of self appears, with the same value, in other.
+    pub fn is_subset_of(&self, other: &ThinStateDiff) -> bool {
+        fn is_sub_map<K: Hash + Eq, V: PartialEq>(
+            map: &IndexMap<K, V>,
+            other: &IndexMap<K, V>,
+        ) -> bool {
+            map.iter().all(|(key, value)| other.get(key) == Some(value))
+        }
+
+        is_sub_map(&self.deployed_contracts, &other.deployed_contracts)
+            && self.storage_diffs.iter().all(|(address, storage_entries)| {
+                storage_entries.is_empty()
+                    || other
+                        .storage_diffs
+                        .get(address)
+                        .is_some_and(|other_entries| is_sub_map(storage_entries, other_entries))
+            })
+            && is_sub_map(&self.declared_classes, &other.declared_classes)
+            && self
+                .deprecated_declared_classes
+                .iter()
+                .all(|class_hash| other.deprecated_declared_classes.contains(class_hash))
+            && is_sub_map(&self.nonces, &other.nonces)
+            && is_sub_map(&self.replaced_classes, &other.replaced_classes)
+    }
+}
+
+// Hashes the commitment of the diff, which doesn't depend on the order of the entries, so diffs
+// that are equal hash equally.
+impl Hash for ThinStateDiff {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        calculate_state_diff_hash(self).hash(state);
+    }
+}
+
+// The classes aren't part of the commitment; equal diffs still hash equally.
+impl Hash for StateDiff {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.commitment().hash(state);
+    }
 }
 
 impl From<StateDiff> for ThinStateDiff {
@@ -120,6 +358,106 @@ impl From<StateDiff> for ThinStateDiff {
     }
 }
 
+impl From<&StateDiff> for ThinStateDiff {
+    fn from(diff: &StateDiff) -> Self {
+        Self {
+            deployed_contracts: diff.deployed_contracts.clone(),
+            storage_diffs: diff.storage_diffs.clone(),
+            declared_classes: diff
+                .declared_classes
+                .iter()
+                .map(|(class_hash, (compiled_hash, _class))| (*class_hash, *compiled_hash))
+                .collect(),
+            deprecated_declared_classes: diff.deprecated_declared_classes.keys().copied().collect(),
+            nonces: diff.nonces.clone(),
+            replaced_classes: diff.replaced_classes.clone(),
+        }
+    }
+}
+
+impl StateDiff {
+    /// Returns the commitment of the state diff, as in the block header. The classes aren't part
+    /// of the commitment, so they aren't cloned.
+    pub fn commitment(&self) -> StateDiffCommitment {
+        calculate_state_diff_hash(&ThinStateDiff::from(self))
+    }
+
+    /// Returns the kind of the class declared in this diff with the given hash, or None if it isn't
+    /// declared in this diff.
+    pub fn class_kind(&self, class_hash: ClassHash) -> Option<ClassKind> {
+        if self.declared_classes.contains_key(&class_hash) {
+            Some(ClassKind::Sierra)
+        } else if self.deprecated_declared_classes.contains_key(&class_hash) {
+            Some(ClassKind::Deprecated)
+        } else {
+            None
+        }
+    }
+
+    /// Returns a diff that undoes the storage and nonce updates of this diff, given the values
+    /// prior to it, e.g., for reverting a block during a reorg.
+    ///
+    /// Only storage values and nonces are inverted. Deployments, class declarations and class
+    /// replacements can't be undone by applying a diff, so they're left out of the result and
+    /// must be reverted by the caller.
+    pub fn invert(
+        &self,
+        prior_storage_value: impl Fn(ContractAddress, StorageKey) -> Felt,
+        prior_nonce: impl Fn(ContractAddress) -> Nonce,
+    ) -> StateDiff {
+        StateDiff {
+            storage_diffs: self
+                .storage_diffs
+                .iter()
+                .map(|(address, storage_entries)| {
+                    let prior_entries = storage_entries
+                        .keys()
+                        .map(|key| (*key, prior_storage_value(*address, *key)))
+                        .collect();
+                    (*address, prior_entries)
+                })
+                .collect(),
+            nonces: self.nonces.keys().map(|address| (*address, prior_nonce(*address))).collect(),
+            ..Default::default()
+        }
+    }
+}
+
+// The state diff in the format of the feeder gateway.
+#[derive(Deserialize)]
+struct GatewayStateDiff {
+    #[serde(default)]
+    storage_diffs: IndexMap<ContractAddress, Vec<GatewayStorageEntry>>,
+    #[serde(default)]
+    nonces: IndexMap<ContractAddress, Nonce>,
+    #[serde(default)]
+    deployed_contracts: Vec<GatewayContractClassHash>,
+    #[serde(default)]
+    old_declared_contracts: Vec<ClassHash>,
+    #[serde(default)]
+    declared_classes: Vec<GatewayDeclaredClass>,
+    #[serde(default)]
+    replaced_classes: Vec<GatewayContractClassHash>,
+}
+
+#[derive(Deserialize)]
+struct GatewayStorageEntry {
+    key: StorageKey,
+    value: Felt,
+}
+
+#[derive(Deserialize)]
+struct GatewayContractClassHash {
+    address: ContractAddress,
+    class_hash: ClassHash,
+}
+
+#[derive(Deserialize)]
+struct GatewayDeclaredClass {
+    class_hash: ClassHash,
+    compiled_class_hash: CompiledClassHash,
+}
+
 /// The sequential numbering of the states between blocks.
 // Example:
 // States: S0       S1       S2
@@ -127,6 +465,7 @@ impl From<StateDiff> for ThinStateDiff {
 #[derive(
     Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord,
 )]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct StateNumber(pub BlockNumber);
 
 impl StateNumber {
@@ -156,6 +495,22 @@ impl StateNumber {
     pub fn block_after(&self) -> BlockNumber {
         self.0
     }
+
+    /// Returns the next state number, i.e., the state right after [`Self::block_after`], or None
+    /// if it's out of range.
+    pub fn next(&self) -> Option<Self> {
+        Some(Self(self.0.next()?))
+    }
+
+    /// Returns the previous state number, or None if self is the state before the first block.
+    pub fn prev(&self) -> Option<Self> {
+        Some(Self(self.0.prev()?))
+    }
+
+    /// Returns an iterator over the state numbers from self to up_to (exclusive).
+    pub fn iter_up_to(&self, up_to: Self) -> impl Iterator<Item = StateNumber> {
+        self.0.iter_up_to(up_to.0).map(Self)
+    }
 }
 
 /// A storage key in a contract.
@@ -172,9 +527,14 @@ impl StateNumber {
     PartialOrd,
     Ord,
     derive_more::Deref,
+    derive_more::Display,
 )]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct StorageKey(pub PatriciaKey);
 
+impl_inner_conversions!(StorageKey: PatriciaKey);
+
 impl From<StorageKey> for Felt {
     fn from(storage_key: StorageKey) -> Felt {
         **storage_key
@@ -199,15 +559,39 @@ impl_from_through_intermediate!(u128, StorageKey, u8, u16, u32, u64);
 
 /// A contract class.
 #[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ContractClass {
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::borsh_codec::serialize_felts",
+            deserialize_with = "crate::borsh_codec::deserialize_felts"
+        )
+    )]
+    #[cfg_attr(feature = "schemars", schemars(with = "Vec<FeltAsHex>"))]
     pub sierra_program: Vec<Felt>,
-    pub entry_points_by_type: HashMap<EntryPointType, Vec<EntryPoint>>,
+    pub entry_points_by_type: EntryPointsByType<EntryPoint>,
     pub abi: String,
 }
 
+impl ContractClass {
+    /// Parses the ABI of the class into the items of the Cairo 1 compiler. An empty ABI has no
+    /// items.
+    pub fn parse_abi(&self) -> Result<Vec<abi::Item>, StarknetApiError> {
+        if self.abi.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+        serde_json::from_str(&self.abi).map_err(|err| StarknetApiError::InvalidAbi(err.to_string()))
+    }
+}
+
+/// An entry point type of a contract class.
 #[derive(
     Debug, Default, Clone, Copy, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord,
 )]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(deny_unknown_fields)]
 pub enum EntryPointType {
     /// A constructor entry point.
@@ -222,8 +606,62 @@ pub enum EntryPointType {
     L1Handler,
 }
 
+/// The entry points of a contract class, by their type.
+///
+/// Backed by a [BTreeMap], so iteration is in the order of [EntryPointType], regardless of the
+/// order in which the entry points were inserted or deserialized.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize)]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(transparent)]
+pub struct EntryPointsByType<EP>(pub BTreeMap<EntryPointType, Vec<EP>>);
+
+impl<EP> EntryPointsByType<EP> {
+    /// Returns the entry points of the given type, or an empty slice if there are none.
+    pub fn get(&self, entry_point_type: EntryPointType) -> &[EP] {
+        self.0.get(&entry_point_type).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    pub fn constructor(&self) -> &[EP] {
+        self.get(EntryPointType::Constructor)
+    }
+
+    pub fn external(&self) -> &[EP] {
+        self.get(EntryPointType::External)
+    }
+
+    pub fn l1_handler(&self) -> &[EP] {
+        self.get(EntryPointType::L1Handler)
+    }
+
+    /// Iterates over the entry points, ordered by their type.
+    pub fn iter(&self) -> impl Iterator<Item = (&EntryPointType, &Vec<EP>)> {
+        self.0.iter()
+    }
+}
+
+impl<EP> From<BTreeMap<EntryPointType, Vec<EP>>> for EntryPointsByType<EP> {
+    fn from(entry_points_by_type: BTreeMap<EntryPointType, Vec<EP>>) -> Self {
+        Self(entry_points_by_type)
+    }
+}
+
+impl<EP> From<HashMap<EntryPointType, Vec<EP>>> for EntryPointsByType<EP> {
+    fn from(entry_points_by_type: HashMap<EntryPointType, Vec<EP>>) -> Self {
+        Self(entry_points_by_type.into_iter().collect())
+    }
+}
+
+impl<EP> FromIterator<(EntryPointType, Vec<EP>)> for EntryPointsByType<EP> {
+    fn from_iter<I: IntoIterator<Item = (EntryPointType, Vec<EP>)>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
 /// An entry point of a [ContractClass](`crate::state::ContractClass`).
 #[derive(Debug, Default, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct EntryPoint {
     pub function_idx: FunctionIndex,
     pub selector: EntryPointSelector,
@@ -232,4 +670,6 @@ pub struct EntryPoint {
 #[derive(
     Debug, Copy, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord,
 )]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct FunctionIndex(pub usize);
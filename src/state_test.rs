@@ -1,11 +1,22 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
+use assert_matches::assert_matches;
+use cairo_lang_starknet_classes::abi;
 use indexmap::{indexmap, IndexMap};
 use serde_json::json;
+use starknet_types_core::felt::Felt;
 
-use super::ThinStateDiff;
-use crate::core::{ClassHash, CompiledClassHash, Nonce};
-use crate::deprecated_contract_class::EntryPointOffset;
+use super::{
+    ClassKind, ContractClass, EntryPoint, EntryPointType, EntryPointsByType, FunctionIndex,
+    GatewayStateUpdate, StateDiff, StateNumber, StorageKey, ThinStateDiff,
+};
+use crate::block::{BlockHash, BlockNumber};
+use crate::core::{
+    ClassHash, CompiledClassHash, ContractAddress, EntryPointSelector, GlobalRoot, Nonce,
+    PatriciaKey,
+};
+use crate::deprecated_contract_class::{ContractClass as DeprecatedContractClass, EntryPointOffset};
+use crate::{contract_address, felt, patricia_key, StarknetApiError};
 
 #[test]
 fn entry_point_offset_from_json_str() {
@@ -116,3 +127,430 @@ fn thin_state_diff_is_empty() {
         .is_empty()
     );
 }
+
+#[test]
+fn entry_points_by_type_iteration_order() {
+    let entry_point = |idx| EntryPoint {
+        function_idx: FunctionIndex(idx),
+        selector: EntryPointSelector(Felt::from(idx)),
+    };
+    let entry_points_by_type: EntryPointsByType<EntryPoint> = serde_json::from_value(json!({
+        "L1_HANDLER": [entry_point(2)],
+        "EXTERNAL": [entry_point(1)],
+        "CONSTRUCTOR": [entry_point(0)],
+    }))
+    .unwrap();
+
+    let types: Vec<_> = entry_points_by_type.iter().map(|(ty, _)| *ty).collect();
+    assert_eq!(
+        types,
+        vec![EntryPointType::Constructor, EntryPointType::External, EntryPointType::L1Handler]
+    );
+    assert_eq!(entry_points_by_type.constructor(), &[entry_point(0)]);
+    assert_eq!(entry_points_by_type.external(), &[entry_point(1)]);
+    assert_eq!(entry_points_by_type.l1_handler(), &[entry_point(2)]);
+
+    let from_hash_map = EntryPointsByType::from(HashMap::from([
+        (EntryPointType::L1Handler, vec![entry_point(2)]),
+        (EntryPointType::External, vec![entry_point(1)]),
+        (EntryPointType::Constructor, vec![entry_point(0)]),
+    ]));
+    assert_eq!(from_hash_map, entry_points_by_type);
+    assert_eq!(
+        serde_json::to_string(&from_hash_map).unwrap(),
+        serde_json::to_string(&entry_points_by_type).unwrap()
+    );
+}
+
+#[test]
+fn entry_points_by_type_missing_type_is_empty() {
+    let entry_points_by_type: EntryPointsByType<EntryPoint> =
+        serde_json::from_value(json!({ "EXTERNAL": [] })).unwrap();
+    assert!(entry_points_by_type.constructor().is_empty());
+    assert!(entry_points_by_type.l1_handler().is_empty());
+}
+
+#[test]
+fn state_number_iteration() {
+    let first = StateNumber::right_before_block(BlockNumber(2));
+    let last = StateNumber::right_after_block(BlockNumber(4)).unwrap();
+    let states: Vec<_> = first.iter_up_to(last).collect();
+    assert_eq!(
+        states,
+        vec![StateNumber(BlockNumber(2)), StateNumber(BlockNumber(3)), StateNumber(BlockNumber(4))]
+    );
+    // The state right after the last block isn't yielded.
+    assert!(!states.contains(&last));
+    assert_eq!(last.iter_up_to(first).count(), 0);
+
+    assert_eq!(first.next(), Some(StateNumber(BlockNumber(3))));
+    assert_eq!(first.next().unwrap().prev(), Some(first));
+    assert_eq!(StateNumber::default().prev(), None);
+    assert_eq!(StateNumber(BlockNumber(u64::MAX)).next(), None);
+}
+
+#[test]
+fn state_number_boundaries() {
+    let block_number = BlockNumber(7);
+    let before = StateNumber::right_before_block(block_number);
+    let after = StateNumber::right_after_block(block_number).unwrap();
+
+    assert_eq!(before.next(), Some(after));
+    assert_eq!(after.prev(), Some(before));
+    assert!(before.is_before(block_number));
+    assert!(!before.is_after(block_number));
+    assert!(after.is_after(block_number));
+    assert!(!after.is_before(block_number));
+    assert!(after.is_before(block_number.unchecked_next()));
+
+    // Every state in the range is right before the block it precedes, and after all the blocks
+    // before it.
+    for state_number in StateNumber::default().iter_up_to(after) {
+        assert!(state_number.is_before(state_number.block_after()));
+        if let Some(prev_block) = state_number.block_after().prev() {
+            assert!(state_number.is_after(prev_block));
+        }
+        assert!(state_number.is_before(block_number));
+    }
+}
+
+#[test]
+fn storage_key_inner_conversions() {
+    let patricia_key = patricia_key!("0x1234");
+    let storage_key = StorageKey(patricia_key);
+    assert_eq!(storage_key.into_inner(), patricia_key);
+    assert_eq!(storage_key.as_inner(), &patricia_key);
+    assert_eq!(PatriciaKey::from(storage_key), patricia_key);
+    assert_eq!(Felt::from(storage_key), felt!("0x1234"));
+}
+
+#[test]
+fn thin_state_diff_is_subset_of() {
+    let state_diff = ThinStateDiff {
+        deployed_contracts: indexmap! { 1u64.into() => ClassHash(felt!("0x10")) },
+        storage_diffs: indexmap! {
+            1u64.into() => indexmap! { 2u64.into() => felt!("0x20"), 3u64.into() => felt!("0x30") },
+        },
+        declared_classes: indexmap! { ClassHash(felt!("0x4")) => CompiledClassHash(felt!("0x40")) },
+        deprecated_declared_classes: vec![ClassHash(felt!("0x5")), ClassHash(felt!("0x6"))],
+        nonces: indexmap! { 1u64.into() => Nonce(felt!("0x1")) },
+        replaced_classes: indexmap! { 2u64.into() => ClassHash(felt!("0x7")) },
+    };
+    assert!(state_diff.is_subset_of(&state_diff));
+    assert!(ThinStateDiff::default().is_subset_of(&state_diff));
+
+    let subset = ThinStateDiff {
+        storage_diffs: indexmap! {
+            1u64.into() => indexmap! { 3u64.into() => felt!("0x30") },
+            // Empty storage diffs are trivially contained.
+            5u64.into() => indexmap! {},
+        },
+        deprecated_declared_classes: vec![ClassHash(felt!("0x6"))],
+        nonces: state_diff.nonces.clone(),
+        ..Default::default()
+    };
+    assert!(subset.is_subset_of(&state_diff));
+    assert!(!state_diff.is_subset_of(&subset));
+
+    // Same key with a different value.
+    let different_value = ThinStateDiff {
+        storage_diffs: indexmap! { 1u64.into() => indexmap! { 3u64.into() => felt!("0x31") } },
+        ..Default::default()
+    };
+    assert!(!different_value.is_subset_of(&state_diff));
+    let different_nonce =
+        ThinStateDiff { nonces: indexmap! { 1u64.into() => Nonce(felt!("0x2")) }, ..subset };
+    assert!(!different_nonce.is_subset_of(&state_diff));
+}
+
+#[test]
+fn thin_state_diff_squash() {
+    let first = ThinStateDiff {
+        deployed_contracts: indexmap! { 3u64.into() => ClassHash(felt!("0x30")) },
+        storage_diffs: indexmap! {
+            3u64.into() => indexmap! { 2u64.into() => felt!("0x1"), 1u64.into() => felt!("0x2") },
+        },
+        declared_classes: indexmap! { ClassHash(felt!("0x4")) => CompiledClassHash(felt!("0x40")) },
+        deprecated_declared_classes: vec![ClassHash(felt!("0x5"))],
+        nonces: indexmap! { 3u64.into() => Nonce(felt!("0x1")) },
+        replaced_classes: indexmap! { 2u64.into() => ClassHash(felt!("0x20")) },
+    };
+    let second = ThinStateDiff {
+        storage_diffs: indexmap! {
+            3u64.into() => indexmap! { 1u64.into() => felt!("0x3") },
+            1u64.into() => indexmap! { 1u64.into() => felt!("0x4") },
+        },
+        declared_classes: indexmap! { ClassHash(felt!("0x6")) => CompiledClassHash(felt!("0x60")) },
+        deprecated_declared_classes: vec![ClassHash(felt!("0x5")), ClassHash(felt!("0x7"))],
+        nonces: indexmap! {
+            3u64.into() => Nonce(felt!("0x2")),
+            1u64.into() => Nonce(felt!("0x1")),
+        },
+        replaced_classes: indexmap! {
+            3u64.into() => ClassHash(felt!("0x31")),
+            2u64.into() => ClassHash(felt!("0x21")),
+        },
+        ..Default::default()
+    };
+
+    let expected = ThinStateDiff {
+        // The class replaced after the deployment is squashed into it.
+        deployed_contracts: indexmap! { 3u64.into() => ClassHash(felt!("0x31")) },
+        storage_diffs: indexmap! {
+            1u64.into() => indexmap! { 1u64.into() => felt!("0x4") },
+            3u64.into() => indexmap! { 1u64.into() => felt!("0x3"), 2u64.into() => felt!("0x1") },
+        },
+        declared_classes: indexmap! {
+            ClassHash(felt!("0x4")) => CompiledClassHash(felt!("0x40")),
+            ClassHash(felt!("0x6")) => CompiledClassHash(felt!("0x60")),
+        },
+        deprecated_declared_classes: vec![ClassHash(felt!("0x5")), ClassHash(felt!("0x7"))],
+        nonces: indexmap! {
+            1u64.into() => Nonce(felt!("0x1")),
+            3u64.into() => Nonce(felt!("0x2")),
+        },
+        replaced_classes: indexmap! { 2u64.into() => ClassHash(felt!("0x21")) },
+    };
+    let squashed = ThinStateDiff::squash(&[first.clone(), second]);
+    assert_eq!(squashed, expected);
+    // Index maps compare regardless of order, so the order is checked explicitly.
+    assert!(squashed.storage_diffs.keys().eq([&1u64.into(), &3u64.into()]));
+    let storage_entries = &squashed.storage_diffs[&ContractAddress::from(3u64)];
+    assert!(storage_entries.keys().eq([&1u64.into(), &2u64.into()]));
+    assert!(squashed.nonces.keys().eq([&1u64.into(), &3u64.into()]));
+
+    assert_eq!(ThinStateDiff::squash(std::slice::from_ref(&first)), first);
+    assert_eq!(ThinStateDiff::squash(&[]), ThinStateDiff::default());
+}
+
+#[test]
+fn state_diff_invert() {
+    let prior_storage = HashMap::from([
+        ((ContractAddress::from(1u64), StorageKey::from(1u64)), felt!("0x10")),
+        ((ContractAddress::from(1u64), StorageKey::from(2u64)), felt!("0x20")),
+    ]);
+    // Keys missing from the prior storage weren't set before the diff.
+    let prior_storage_value =
+        |address, key| prior_storage.get(&(address, key)).copied().unwrap_or_default();
+    let prior_nonce = |_address| panic!("The diff has no nonces.");
+
+    let state_diff = StateDiff {
+        storage_diffs: indexmap! {
+            1u64.into() => indexmap! { 1u64.into() => felt!("0x11"), 2u64.into() => felt!("0x21") },
+            2u64.into() => indexmap! { 1u64.into() => felt!("0x1") },
+        },
+        ..Default::default()
+    };
+    let expected = StateDiff {
+        storage_diffs: indexmap! {
+            1u64.into() => indexmap! { 1u64.into() => felt!("0x10"), 2u64.into() => felt!("0x20") },
+            2u64.into() => indexmap! { 1u64.into() => Felt::ZERO },
+        },
+        ..Default::default()
+    };
+    let inverted = state_diff.invert(prior_storage_value, prior_nonce);
+    assert_eq!(inverted, expected);
+
+    // Inverting with the values written by the diff restores it.
+    let written_value = |address, key| state_diff.storage_diffs[&address][&key];
+    assert_eq!(inverted.invert(written_value, prior_nonce), state_diff);
+}
+
+#[test]
+fn state_diff_hash_set() {
+    let state_diff = ThinStateDiff {
+        deployed_contracts: indexmap! {
+            1u64.into() => ClassHash(felt!("0x10")),
+            2u64.into() => ClassHash(felt!("0x20")),
+        },
+        nonces: indexmap! { 1u64.into() => Nonce(felt!("0x1")) },
+        ..Default::default()
+    };
+    // Equal to the diff above, with the entries in a different order.
+    let reordered_state_diff = ThinStateDiff {
+        deployed_contracts: indexmap! {
+            2u64.into() => ClassHash(felt!("0x20")),
+            1u64.into() => ClassHash(felt!("0x10")),
+        },
+        ..state_diff.clone()
+    };
+    assert_eq!(state_diff, reordered_state_diff);
+
+    let state_diffs = HashSet::from([state_diff.clone(), reordered_state_diff]);
+    assert_eq!(state_diffs.len(), 1);
+    assert!(state_diffs.contains(&state_diff));
+    assert!(!state_diffs.contains(&ThinStateDiff::default()));
+
+    let full_state_diffs = HashSet::from([StateDiff::default(), StateDiff::default()]);
+    assert_eq!(full_state_diffs.len(), 1);
+}
+
+#[test]
+fn gateway_state_update_deserialization() {
+    // A state update as returned by the get_state_update endpoint of the feeder gateway.
+    let json = json!({
+        "block_hash": "0x47c3637b57c2b079b93c61539950c17e868a28f46cdef28f88521067f21e943",
+        "new_root": "0x21870ba80540e7831fb21c591ee93481f5ae1bb71ff85a86ddd465be4eddee6",
+        "old_root": "0x5d7f6a0cc4eb4cf5dc1e3f2e3b1c5d5a4f2e6b8b9d1a3c5e7f9b2d4f6a8c0e1",
+        "state_diff": {
+            "storage_diffs": {
+                "0x49d36570d4e46f48e99674bd3fcc84644ddd6b96f7c741b1562b82f9e004dc7": [
+                    { "key": "0x5496768776e3db30053404f18067d81a6e06f5a2b0de326e21298fd9d569a9a",
+                      "value": "0x1b77017df88b0858c9c29" },
+                    { "key": "0x3c204dd68b8e800b4f42e438d9ed4ccbba9f8e436518758cd36553715c1d6ab",
+                      "value": "0x1e6d5d9a6a7a9d2" }
+                ],
+                "0x3": [{ "key": "0x0", "value": "0x1" }]
+            },
+            "nonces": {
+                "0x6c6c4b1e9b6e1a5c7f1f1e8a2b5d3c4e6f7a8b9c0d1e2f3a4b5c6d7e8f9a0b1": "0x2c"
+            },
+            "deployed_contracts": [
+                {
+                    "address": "0x5f9c3c1b2a4d6e8f0a1b3c5d7e9f1a2b4c6d8e0f1a3b5c7d9e1f2a4b6c8d0e",
+                    "class_hash":
+                        "0x1a736d6ed154502257f02b1ccdf4d9d1089f80811cd6acad48e6b6a9d1f2003"
+                }
+            ],
+            "old_declared_contracts": [
+                "0x2760f25d5a4fb2bdde5f561fd0b44a3dee78c28903577d37d669939d97036a0"
+            ],
+            "declared_classes": [
+                { "class_hash": "0x29927c8af6bccf3f6fda035981e765a7bdbf18a2dc0d630494f8758aa908e2b",
+                  "compiled_class_hash":
+                    "0x1cd2be5e2fa2d5a2c56a6d5d1d0d9ddbc1f0ba9f5b9b3e7e51d2e0d5d72e1a0" }
+            ],
+            "replaced_classes": []
+        }
+    });
+
+    let state_update: GatewayStateUpdate = serde_json::from_value(json).unwrap();
+    assert_eq!(
+        state_update.block_hash,
+        BlockHash(felt!("0x47c3637b57c2b079b93c61539950c17e868a28f46cdef28f88521067f21e943"))
+    );
+    assert_eq!(
+        state_update.new_root,
+        GlobalRoot(felt!("0x21870ba80540e7831fb21c591ee93481f5ae1bb71ff85a86ddd465be4eddee6"))
+    );
+
+    let state_diff = state_update.state_diff;
+    assert_eq!(state_diff.len(), 7);
+    // The storage diffs are sorted by address and by key.
+    assert_eq!(state_diff.storage_diffs.keys().next(), Some(&3u64.into()));
+    let storage_entries = &state_diff.storage_diffs
+        [&contract_address!("0x49d36570d4e46f48e99674bd3fcc84644ddd6b96f7c741b1562b82f9e004dc7")];
+    assert_eq!(
+        storage_entries.first(),
+        Some((
+            &StorageKey(patricia_key!(
+                "0x3c204dd68b8e800b4f42e438d9ed4ccbba9f8e436518758cd36553715c1d6ab"
+            )),
+            &felt!("0x1e6d5d9a6a7a9d2")
+        ))
+    );
+    assert_eq!(
+        state_diff.nonces[&contract_address!(
+            "0x6c6c4b1e9b6e1a5c7f1f1e8a2b5d3c4e6f7a8b9c0d1e2f3a4b5c6d7e8f9a0b1"
+        )],
+        Nonce(felt!("0x2c"))
+    );
+    assert_eq!(
+        state_diff.deployed_contracts[&contract_address!(
+            "0x5f9c3c1b2a4d6e8f0a1b3c5d7e9f1a2b4c6d8e0f1a3b5c7d9e1f2a4b6c8d0e"
+        )],
+        ClassHash(felt!("0x1a736d6ed154502257f02b1ccdf4d9d1089f80811cd6acad48e6b6a9d1f2003"))
+    );
+    assert_eq!(
+        state_diff.deprecated_declared_classes,
+        vec![ClassHash(felt!("0x2760f25d5a4fb2bdde5f561fd0b44a3dee78c28903577d37d669939d97036a0"))]
+    );
+    let declared_class_hash =
+        ClassHash(felt!("0x29927c8af6bccf3f6fda035981e765a7bdbf18a2dc0d630494f8758aa908e2b"));
+    assert_eq!(
+        state_diff.declared_classes[&declared_class_hash],
+        CompiledClassHash(felt!(
+            "0x1cd2be5e2fa2d5a2c56a6d5d1d0d9ddbc1f0ba9f5b9b3e7e51d2e0d5d72e1a0"
+        ))
+    );
+    assert!(state_diff.replaced_classes.is_empty());
+}
+
+#[test]
+fn state_diff_class_kind() {
+    let state_diff = StateDiff {
+        declared_classes: indexmap! {
+            ClassHash(felt!("0x1")) => (CompiledClassHash(felt!("0x10")), ContractClass::default()),
+        },
+        deprecated_declared_classes: indexmap! {
+            ClassHash(felt!("0x2")) => DeprecatedContractClass::default(),
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(state_diff.class_kind(ClassHash(felt!("0x1"))), Some(ClassKind::Sierra));
+    assert_eq!(state_diff.class_kind(ClassHash(felt!("0x2"))), Some(ClassKind::Deprecated));
+    assert_eq!(state_diff.class_kind(ClassHash(felt!("0x3"))), None);
+}
+
+#[test]
+fn contract_class_parse_abi() {
+    let abi = json!([
+        {
+            "type": "function",
+            "name": "get_balance",
+            "inputs": [
+                { "name": "account", "type": "core::starknet::contract_address::ContractAddress" }
+            ],
+            "outputs": [{ "type": "core::felt252" }],
+            "state_mutability": "view"
+        },
+        {
+            "type": "event",
+            "name": "balance::Transfer",
+            "kind": "struct",
+            "members": [
+                { "name": "from", "type": "core::felt252", "kind": "key" },
+                { "name": "amount", "type": "core::felt252", "kind": "data" }
+            ]
+        }
+    ]);
+    let contract_class = ContractClass { abi: abi.to_string(), ..Default::default() };
+
+    let expected = vec![
+        abi::Item::Function(abi::Function {
+            name: "get_balance".to_string(),
+            inputs: vec![abi::Input {
+                name: "account".to_string(),
+                ty: "core::starknet::contract_address::ContractAddress".to_string(),
+            }],
+            outputs: vec![abi::Output { ty: "core::felt252".to_string() }],
+            state_mutability: abi::StateMutability::View,
+        }),
+        abi::Item::Event(abi::Event {
+            name: "balance::Transfer".to_string(),
+            kind: abi::EventKind::Struct {
+                members: vec![
+                    abi::EventField {
+                        name: "from".to_string(),
+                        ty: "core::felt252".to_string(),
+                        kind: abi::EventFieldKind::KeySerde,
+                    },
+                    abi::EventField {
+                        name: "amount".to_string(),
+                        ty: "core::felt252".to_string(),
+                        kind: abi::EventFieldKind::DataSerde,
+                    },
+                ],
+            },
+        }),
+    ];
+    let parsed_abi = contract_class.parse_abi().unwrap();
+    assert_eq!(parsed_abi, expected);
+    assert_eq!(serde_json::to_value(&parsed_abi).unwrap(), abi);
+
+    assert_eq!(ContractClass::default().parse_abi().unwrap(), vec![]);
+    let invalid_class =
+        ContractClass { abi: json!([{ "type": "unknown" }]).to_string(), ..Default::default() };
+    assert_matches!(invalid_class.parse_abi(), Err(StarknetApiError::InvalidAbi(_)));
+}
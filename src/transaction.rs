@@ -1,28 +1,40 @@
+#[cfg(test)]
+#[path = "transaction_test.rs"]
+mod transaction_test;
+
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt::Display;
+use std::ops::{Add, AddAssign};
 use std::sync::Arc;
 
 use derive_more::{Display, From};
+use indexmap::IndexMap;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use starknet_types_core::felt::Felt;
 use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
 
-use crate::block::{BlockHash, BlockNumber};
+use crate::block::{BlockHash, BlockNumber, GasPrice};
+use crate::block_hash::block_hash_calculator::TransactionOutputForHash;
+use crate::block_hash::receipt_commitment::ReceiptElement;
 use crate::core::{
-    ChainId, ClassHash, CompiledClassHash, ContractAddress, EntryPointSelector, EthAddress, Nonce,
+    calculate_contract_address, ChainId, ClassHash, CompiledClassHash, ContractAddress,
+    EntryPointSelector, EthAddress, Nonce,
 };
+use crate::crypto::utils::HashChain;
 use crate::data_availability::DataAvailabilityMode;
 use crate::hash::StarkHash;
-use crate::serde_utils::PrefixedBytesAsHex;
 use crate::transaction_hash::{
     get_declare_transaction_v0_hash, get_declare_transaction_v1_hash,
     get_declare_transaction_v2_hash, get_declare_transaction_v3_hash,
     get_deploy_account_transaction_v1_hash, get_deploy_account_transaction_v3_hash,
     get_deploy_transaction_hash, get_invoke_transaction_v0_hash, get_invoke_transaction_v1_hash,
-    get_invoke_transaction_v3_hash, get_l1_handler_transaction_hash,
+    get_invoke_transaction_v3_hash, get_l1_handler_transaction_hash, get_transaction_hash,
+};
+use crate::{
+    impl_from_dec_or_hex_str, impl_from_hex_str, impl_from_through_intermediate, impl_hex_display,
+    impl_hex_serialized_int, impl_inner_conversions, StarknetApiError,
 };
-use crate::StarknetApiError;
 
 pub trait TransactionHasher {
     fn calculate_transaction_hash(
@@ -34,6 +46,9 @@ pub trait TransactionHasher {
 
 /// A transaction.
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum Transaction {
     /// A declare transaction.
     Declare(DeclareTransaction),
@@ -47,7 +62,50 @@ pub enum Transaction {
     L1Handler(L1HandlerTransaction),
 }
 
+/// The type of a [Transaction], independent of its version.
+///
+/// The string form, e.g., `DEPLOY_ACCOUNT`, is the same for serde and for [Display] and
+/// [FromStr](`std::str::FromStr`).
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Deserialize,
+    EnumIter,
+    Eq,
+    Hash,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    Serialize,
+    strum_macros::Display,
+    strum_macros::EnumString,
+)]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum TransactionType {
+    #[serde(rename = "DECLARE")]
+    Declare,
+    #[serde(rename = "DEPLOY")]
+    Deploy,
+    #[serde(rename = "DEPLOY_ACCOUNT")]
+    DeployAccount,
+    #[serde(rename = "INVOKE")]
+    Invoke,
+    #[serde(rename = "L1_HANDLER")]
+    L1Handler,
+}
+
 impl Transaction {
+    pub fn transaction_type(&self) -> TransactionType {
+        match self {
+            Transaction::Declare(_) => TransactionType::Declare,
+            Transaction::Deploy(_) => TransactionType::Deploy,
+            Transaction::DeployAccount(_) => TransactionType::DeployAccount,
+            Transaction::Invoke(_) => TransactionType::Invoke,
+            Transaction::L1Handler(_) => TransactionType::L1Handler,
+        }
+    }
+
     pub fn version(&self) -> TransactionVersion {
         match self {
             Transaction::Declare(tx) => tx.version(),
@@ -57,8 +115,90 @@ impl Transaction {
             Transaction::L1Handler(tx) => tx.version,
         }
     }
+
+    /// Calculates the hash of the transaction according to its own version.
+    ///
+    /// To hash a transaction as a different version, e.g., for deprecated hashes, use
+    /// [get_transaction_hash].
+    pub fn calculate_hash(&self, chain_id: &ChainId) -> Result<TransactionHash, StarknetApiError> {
+        get_transaction_hash(self, chain_id, &self.version())
+    }
+
+    /// Returns an upper bound on the fee the transaction may be charged, in a block with the given
+    /// L1 gas price.
+    ///
+    /// For transactions up to V2 this is the max fee. For V3 transactions, it's the sum of the max
+    /// amount of each resource times its price, where the L1 gas price is capped by the given
+    /// price. Deploy and L1 handler transactions don't pay a fee on L2, so their bound is zero.
+    pub fn max_fee_upper_bound(&self, l1_gas_price: GasPrice) -> Result<Fee, StarknetApiError> {
+        let resource_bounds = match self {
+            Transaction::Declare(DeclareTransaction::V0(tx) | DeclareTransaction::V1(tx)) => {
+                return Ok(tx.max_fee);
+            }
+            Transaction::Declare(DeclareTransaction::V2(tx)) => return Ok(tx.max_fee),
+            Transaction::Declare(DeclareTransaction::V3(tx)) => &tx.resource_bounds,
+            Transaction::DeployAccount(DeployAccountTransaction::V1(tx)) => return Ok(tx.max_fee),
+            Transaction::DeployAccount(DeployAccountTransaction::V3(tx)) => &tx.resource_bounds,
+            Transaction::Invoke(InvokeTransaction::V0(tx)) => return Ok(tx.max_fee),
+            Transaction::Invoke(InvokeTransaction::V1(tx)) => return Ok(tx.max_fee),
+            Transaction::Invoke(InvokeTransaction::V3(tx)) => &tx.resource_bounds,
+            Transaction::Deploy(_) | Transaction::L1Handler(_) => return Ok(Fee::default()),
+        };
+        resource_bounds.max_possible_fee(l1_gas_price).ok_or_else(|| {
+            let string = format!("Max possible fee of {resource_bounds:?}");
+            StarknetApiError::OutOfRange { string }
+        })
+    }
 }
 
+macro_rules! impl_transaction_variant_conversions {
+    ($($variant:ident: $type:ty),+ $(,)?) => {
+        $(
+            impl From<$type> for Transaction {
+                fn from(tx: $type) -> Self {
+                    Transaction::$variant(tx)
+                }
+            }
+
+            impl TryFrom<Transaction> for $type {
+                type Error = StarknetApiError;
+
+                fn try_from(tx: Transaction) -> Result<Self, Self::Error> {
+                    match tx {
+                        Transaction::$variant(tx) => Ok(tx),
+                        _ => Err(StarknetApiError::UnexpectedTransactionType {
+                            expected: TransactionType::$variant,
+                            actual: tx.transaction_type(),
+                        }),
+                    }
+                }
+            }
+
+            impl TryFrom<&Transaction> for $type {
+                type Error = StarknetApiError;
+
+                fn try_from(tx: &Transaction) -> Result<Self, Self::Error> {
+                    match tx {
+                        Transaction::$variant(tx) => Ok(tx.clone()),
+                        _ => Err(StarknetApiError::UnexpectedTransactionType {
+                            expected: TransactionType::$variant,
+                            actual: tx.transaction_type(),
+                        }),
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_transaction_variant_conversions!(
+    Declare: DeclareTransaction,
+    Deploy: DeployTransaction,
+    DeployAccount: DeployAccountTransaction,
+    Invoke: InvokeTransaction,
+    L1Handler: L1HandlerTransaction,
+);
+
 impl TransactionHasher for Transaction {
     fn calculate_transaction_hash(
         &self,
@@ -81,8 +221,292 @@ impl TransactionHasher for Transaction {
     }
 }
 
+/// A [Transaction] along with its hash, computed once on construction.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct HashedTransaction {
+    tx: Transaction,
+    hash: TransactionHash,
+}
+
+impl HashedTransaction {
+    /// Computes the hash of the transaction on the given chain, according to its version.
+    pub fn new(tx: Transaction, chain_id: &ChainId) -> Result<Self, StarknetApiError> {
+        let hash = tx.calculate_transaction_hash(chain_id, &tx.version())?;
+        Ok(Self { tx, hash })
+    }
+
+    pub fn transaction(&self) -> &Transaction {
+        &self.tx
+    }
+
+    pub fn hash(&self) -> TransactionHash {
+        self.hash
+    }
+
+    pub fn into_transaction(self) -> Transaction {
+        self.tx
+    }
+}
+
+/// Uniform access to the fields shared by some of the transactions. Each getter returns `None` for
+/// transactions that don't have the field, e.g., L1 handler transactions have no signature.
+pub trait TransactionInfo {
+    /// The hash of the transaction, if it's known, i.e., for a [HashedTransaction].
+    fn transaction_hash(&self) -> Option<TransactionHash> {
+        None
+    }
+    fn signature(&self) -> Option<TransactionSignature>;
+    fn nonce(&self) -> Option<Nonce>;
+    /// The account that sends the transaction. Deploy account transactions have no sender field;
+    /// the address of the account is derived from the transaction.
+    fn sender_address(&self) -> Option<ContractAddress>;
+    /// The max fee of transactions up to V2.
+    fn max_fee(&self) -> Option<Fee>;
+    /// The resource bounds of V3 transactions.
+    fn resource_bounds(&self) -> Option<ResourceBoundsMapping>;
+    /// The tip of V3 transactions.
+    fn tip(&self) -> Option<Tip>;
+}
+
+macro_rules! impl_transaction_info {
+    (
+        $type:ty,
+        signature: $signature:ident,
+        nonce: $nonce:ident,
+        sender_address: $sender_address:ident,
+        max_fee: $max_fee:ident,
+        resource_bounds: $resource_bounds:ident,
+        tip: $tip:ident
+    ) => {
+        impl TransactionInfo for $type {
+            fn signature(&self) -> Option<TransactionSignature> {
+                impl_transaction_info!(@field self, $signature)
+            }
+
+            fn nonce(&self) -> Option<Nonce> {
+                impl_transaction_info!(@field self, $nonce)
+            }
+
+            fn sender_address(&self) -> Option<ContractAddress> {
+                impl_transaction_info!(@field self, $sender_address)
+            }
+
+            fn max_fee(&self) -> Option<Fee> {
+                impl_transaction_info!(@field self, $max_fee)
+            }
+
+            fn resource_bounds(&self) -> Option<ResourceBoundsMapping> {
+                impl_transaction_info!(@field self, $resource_bounds)
+            }
+
+            fn tip(&self) -> Option<Tip> {
+                impl_transaction_info!(@field self, $tip)
+            }
+        }
+    };
+    (@field $self:ident, None) => {
+        None
+    };
+    (@field $self:ident, $field:ident) => {
+        Some($self.$field.clone())
+    };
+}
+
+macro_rules! impl_transaction_info_for_enum {
+    ($type:ty, $($variant:path),+ $(,)?) => {
+        impl TransactionInfo for $type {
+            fn signature(&self) -> Option<TransactionSignature> {
+                match self {
+                    $($variant(tx) => TransactionInfo::signature(tx),)+
+                }
+            }
+
+            fn nonce(&self) -> Option<Nonce> {
+                match self {
+                    $($variant(tx) => TransactionInfo::nonce(tx),)+
+                }
+            }
+
+            fn sender_address(&self) -> Option<ContractAddress> {
+                match self {
+                    $($variant(tx) => TransactionInfo::sender_address(tx),)+
+                }
+            }
+
+            fn max_fee(&self) -> Option<Fee> {
+                match self {
+                    $($variant(tx) => TransactionInfo::max_fee(tx),)+
+                }
+            }
+
+            fn resource_bounds(&self) -> Option<ResourceBoundsMapping> {
+                match self {
+                    $($variant(tx) => TransactionInfo::resource_bounds(tx),)+
+                }
+            }
+
+            fn tip(&self) -> Option<Tip> {
+                match self {
+                    $($variant(tx) => TransactionInfo::tip(tx),)+
+                }
+            }
+        }
+    };
+}
+
+impl_transaction_info!(
+    DeclareTransactionV0V1,
+    signature: signature,
+    nonce: nonce,
+    sender_address: sender_address,
+    max_fee: max_fee,
+    resource_bounds: None,
+    tip: None
+);
+impl_transaction_info!(
+    DeclareTransactionV2,
+    signature: signature,
+    nonce: nonce,
+    sender_address: sender_address,
+    max_fee: max_fee,
+    resource_bounds: None,
+    tip: None
+);
+impl_transaction_info!(
+    DeclareTransactionV3,
+    signature: signature,
+    nonce: nonce,
+    sender_address: sender_address,
+    max_fee: None,
+    resource_bounds: resource_bounds,
+    tip: tip
+);
+impl_transaction_info!(
+    DeployTransaction,
+    signature: None,
+    nonce: None,
+    sender_address: None,
+    max_fee: None,
+    resource_bounds: None,
+    tip: None
+);
+impl_transaction_info!(
+    DeployAccountTransactionV1,
+    signature: signature,
+    nonce: nonce,
+    sender_address: None,
+    max_fee: max_fee,
+    resource_bounds: None,
+    tip: None
+);
+impl_transaction_info!(
+    DeployAccountTransactionV3,
+    signature: signature,
+    nonce: nonce,
+    sender_address: None,
+    max_fee: None,
+    resource_bounds: resource_bounds,
+    tip: tip
+);
+// The account of an invoke V0 transaction is the called contract.
+impl_transaction_info!(
+    InvokeTransactionV0,
+    signature: signature,
+    nonce: None,
+    sender_address: contract_address,
+    max_fee: max_fee,
+    resource_bounds: None,
+    tip: None
+);
+impl_transaction_info!(
+    InvokeTransactionV1,
+    signature: signature,
+    nonce: nonce,
+    sender_address: sender_address,
+    max_fee: max_fee,
+    resource_bounds: None,
+    tip: None
+);
+impl_transaction_info!(
+    InvokeTransactionV3,
+    signature: signature,
+    nonce: nonce,
+    sender_address: sender_address,
+    max_fee: None,
+    resource_bounds: resource_bounds,
+    tip: tip
+);
+impl_transaction_info!(
+    L1HandlerTransaction,
+    signature: None,
+    nonce: nonce,
+    sender_address: None,
+    max_fee: None,
+    resource_bounds: None,
+    tip: None
+);
+
+impl_transaction_info_for_enum!(
+    DeclareTransaction,
+    DeclareTransaction::V0,
+    DeclareTransaction::V1,
+    DeclareTransaction::V2,
+    DeclareTransaction::V3,
+);
+impl_transaction_info_for_enum!(
+    DeployAccountTransaction,
+    DeployAccountTransaction::V1,
+    DeployAccountTransaction::V3,
+);
+impl_transaction_info_for_enum!(
+    InvokeTransaction,
+    InvokeTransaction::V0,
+    InvokeTransaction::V1,
+    InvokeTransaction::V3,
+);
+impl_transaction_info_for_enum!(
+    Transaction,
+    Transaction::Declare,
+    Transaction::Deploy,
+    Transaction::DeployAccount,
+    Transaction::Invoke,
+    Transaction::L1Handler,
+);
+
+impl TransactionInfo for HashedTransaction {
+    fn transaction_hash(&self) -> Option<TransactionHash> {
+        Some(self.hash)
+    }
+
+    fn signature(&self) -> Option<TransactionSignature> {
+        TransactionInfo::signature(&self.tx)
+    }
+
+    fn nonce(&self) -> Option<Nonce> {
+        TransactionInfo::nonce(&self.tx)
+    }
+
+    fn sender_address(&self) -> Option<ContractAddress> {
+        TransactionInfo::sender_address(&self.tx)
+    }
+
+    fn max_fee(&self) -> Option<Fee> {
+        TransactionInfo::max_fee(&self.tx)
+    }
+
+    fn resource_bounds(&self) -> Option<ResourceBoundsMapping> {
+        TransactionInfo::resource_bounds(&self.tx)
+    }
+
+    fn tip(&self) -> Option<Tip> {
+        TransactionInfo::tip(&self.tx)
+    }
+}
+
 /// A transaction output.
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum TransactionOutput {
     /// A declare transaction output.
     Declare(DeclareTransactionOutput),
@@ -117,6 +541,20 @@ impl TransactionOutput {
         }
     }
 
+    /// Returns the number of events emitted by the transaction.
+    pub fn event_count(&self) -> usize {
+        self.events().len()
+    }
+
+    /// Returns the events grouped by emitting contract, in order of first emission.
+    pub fn events_by_address(&self) -> IndexMap<ContractAddress, Vec<&Event>> {
+        let mut events_by_address: IndexMap<ContractAddress, Vec<&Event>> = IndexMap::new();
+        for event in self.events() {
+            events_by_address.entry(event.from_address).or_default().push(event);
+        }
+        events_by_address
+    }
+
     pub fn execution_status(&self) -> &TransactionExecutionStatus {
         match self {
             TransactionOutput::Declare(output) => &output.execution_status,
@@ -150,6 +588,9 @@ impl TransactionOutput {
 
 /// A declare V0 or V1 transaction (same schema but different version).
 #[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct DeclareTransactionV0V1 {
     pub max_fee: Fee,
     pub signature: TransactionSignature,
@@ -176,6 +617,9 @@ impl TransactionHasher for DeclareTransactionV0V1 {
 
 /// A declare V2 transaction.
 #[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct DeclareTransactionV2 {
     pub max_fee: Fee,
     pub signature: TransactionSignature,
@@ -197,6 +641,9 @@ impl TransactionHasher for DeclareTransactionV2 {
 
 /// A declare V3 transaction.
 #[derive(Clone, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct DeclareTransactionV3 {
     pub resource_bounds: ResourceBoundsMapping,
     pub tip: Tip,
@@ -222,6 +669,9 @@ impl TransactionHasher for DeclareTransactionV3 {
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum DeclareTransaction {
     V0(DeclareTransactionV0V1),
     V1(DeclareTransactionV0V1),
@@ -242,6 +692,19 @@ macro_rules! implement_declare_tx_getters {
     };
 }
 
+macro_rules! implement_declare_tx_ref_getters {
+    ($(($getter:ident, $field:ident, $field_type:ty)),*) => {
+        $(pub fn $getter(&self) -> &$field_type {
+            match self {
+                Self::V0(tx) => &tx.$field,
+                Self::V1(tx) => &tx.$field,
+                Self::V2(tx) => &tx.$field,
+                Self::V3(tx) => &tx.$field,
+            }
+        })*
+    };
+}
+
 impl DeclareTransaction {
     implement_declare_tx_getters!(
         (class_hash, ClassHash),
@@ -250,6 +713,13 @@ impl DeclareTransaction {
         (signature, TransactionSignature)
     );
 
+    implement_declare_tx_ref_getters!(
+        (class_hash_ref, class_hash, ClassHash),
+        (nonce_ref, nonce, Nonce),
+        (sender_address_ref, sender_address, ContractAddress),
+        (signature_ref, signature, TransactionSignature)
+    );
+
     pub fn version(&self) -> TransactionVersion {
         match self {
             DeclareTransaction::V0(_) => TransactionVersion::ZERO,
@@ -258,6 +728,22 @@ impl DeclareTransaction {
             DeclareTransaction::V3(_) => TransactionVersion::THREE,
         }
     }
+
+    /// The max fee of the transaction, or `None` for V3 transactions, which have resource bounds
+    /// instead.
+    pub fn max_fee(&self) -> Option<Fee> {
+        TransactionInfo::max_fee(self)
+    }
+
+    /// The resource bounds of V3 transactions, or `None` for earlier versions.
+    pub fn resource_bounds(&self) -> Option<ResourceBoundsMapping> {
+        TransactionInfo::resource_bounds(self)
+    }
+
+    /// The tip of V3 transactions, or `None` for earlier versions.
+    pub fn tip(&self) -> Option<Tip> {
+        TransactionInfo::tip(self)
+    }
 }
 
 impl TransactionHasher for DeclareTransaction {
@@ -285,6 +771,9 @@ impl TransactionHasher for DeclareTransaction {
 
 /// A deploy account V1 transaction.
 #[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct DeployAccountTransactionV1 {
     pub max_fee: Fee,
     pub signature: TransactionSignature,
@@ -294,6 +783,18 @@ pub struct DeployAccountTransactionV1 {
     pub constructor_calldata: Calldata,
 }
 
+impl DeployAccountTransactionV1 {
+    /// Calculates the address of the deployed account.
+    pub fn calculate_contract_address(&self) -> Result<ContractAddress, StarknetApiError> {
+        calculate_contract_address(
+            self.contract_address_salt,
+            self.class_hash,
+            &self.constructor_calldata,
+            ContractAddress::default(),
+        )
+    }
+}
+
 impl TransactionHasher for DeployAccountTransactionV1 {
     fn calculate_transaction_hash(
         &self,
@@ -306,6 +807,9 @@ impl TransactionHasher for DeployAccountTransactionV1 {
 
 /// A deploy account V3 transaction.
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct DeployAccountTransactionV3 {
     pub resource_bounds: ResourceBoundsMapping,
     pub tip: Tip,
@@ -319,6 +823,18 @@ pub struct DeployAccountTransactionV3 {
     pub paymaster_data: PaymasterData,
 }
 
+impl DeployAccountTransactionV3 {
+    /// Calculates the address of the deployed account.
+    pub fn calculate_contract_address(&self) -> Result<ContractAddress, StarknetApiError> {
+        calculate_contract_address(
+            self.contract_address_salt,
+            self.class_hash,
+            &self.constructor_calldata,
+            ContractAddress::default(),
+        )
+    }
+}
+
 impl TransactionHasher for DeployAccountTransactionV3 {
     fn calculate_transaction_hash(
         &self,
@@ -330,6 +846,9 @@ impl TransactionHasher for DeployAccountTransactionV3 {
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord, From)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum DeployAccountTransaction {
     V1(DeployAccountTransactionV1),
     V3(DeployAccountTransactionV3),
@@ -348,6 +867,19 @@ macro_rules! implement_deploy_account_tx_getters {
     };
 }
 
+macro_rules! implement_deploy_account_tx_ref_getters {
+    ($(($getter:ident, $field:ident, $field_type:ty)),*) => {
+        $(
+            pub fn $getter(&self) -> &$field_type {
+                match self {
+                    Self::V1(tx) => &tx.$field,
+                    Self::V3(tx) => &tx.$field,
+                }
+            }
+        )*
+    };
+}
+
 impl DeployAccountTransaction {
     implement_deploy_account_tx_getters!(
         (class_hash, ClassHash),
@@ -357,12 +889,44 @@ impl DeployAccountTransaction {
         (signature, TransactionSignature)
     );
 
+    implement_deploy_account_tx_ref_getters!(
+        (class_hash_ref, class_hash, ClassHash),
+        (constructor_calldata_ref, constructor_calldata, Calldata),
+        (contract_address_salt_ref, contract_address_salt, ContractAddressSalt),
+        (nonce_ref, nonce, Nonce),
+        (signature_ref, signature, TransactionSignature)
+    );
+
     pub fn version(&self) -> TransactionVersion {
         match self {
             DeployAccountTransaction::V1(_) => TransactionVersion::ONE,
             DeployAccountTransaction::V3(_) => TransactionVersion::THREE,
         }
     }
+
+    /// The max fee of the transaction, or `None` for V3 transactions, which have resource bounds
+    /// instead.
+    pub fn max_fee(&self) -> Option<Fee> {
+        TransactionInfo::max_fee(self)
+    }
+
+    /// The resource bounds of V3 transactions, or `None` for earlier versions.
+    pub fn resource_bounds(&self) -> Option<ResourceBoundsMapping> {
+        TransactionInfo::resource_bounds(self)
+    }
+
+    /// The tip of V3 transactions, or `None` for earlier versions.
+    pub fn tip(&self) -> Option<Tip> {
+        TransactionInfo::tip(self)
+    }
+
+    /// Calculates the address of the deployed account.
+    pub fn calculate_contract_address(&self) -> Result<ContractAddress, StarknetApiError> {
+        match self {
+            DeployAccountTransaction::V1(tx) => tx.calculate_contract_address(),
+            DeployAccountTransaction::V3(tx) => tx.calculate_contract_address(),
+        }
+    }
 }
 
 impl TransactionHasher for DeployAccountTransaction {
@@ -384,6 +948,9 @@ impl TransactionHasher for DeployAccountTransaction {
 
 /// A deploy transaction.
 #[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct DeployTransaction {
     pub version: TransactionVersion,
     pub class_hash: ClassHash,
@@ -391,6 +958,18 @@ pub struct DeployTransaction {
     pub constructor_calldata: Calldata,
 }
 
+impl DeployTransaction {
+    /// Calculates the address of the deployed contract.
+    pub fn calculate_contract_address(&self) -> Result<ContractAddress, StarknetApiError> {
+        calculate_contract_address(
+            self.contract_address_salt,
+            self.class_hash,
+            &self.constructor_calldata,
+            ContractAddress::default(),
+        )
+    }
+}
+
 impl TransactionHasher for DeployTransaction {
     fn calculate_transaction_hash(
         &self,
@@ -403,6 +982,9 @@ impl TransactionHasher for DeployTransaction {
 
 /// An invoke V0 transaction.
 #[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct InvokeTransactionV0 {
     pub max_fee: Fee,
     pub signature: TransactionSignature,
@@ -423,6 +1005,9 @@ impl TransactionHasher for InvokeTransactionV0 {
 
 /// An invoke V1 transaction.
 #[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct InvokeTransactionV1 {
     pub max_fee: Fee,
     pub signature: TransactionSignature,
@@ -443,6 +1028,9 @@ impl TransactionHasher for InvokeTransactionV1 {
 
 /// An invoke V3 transaction.
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct InvokeTransactionV3 {
     pub resource_bounds: ResourceBoundsMapping,
     pub tip: Tip,
@@ -467,6 +1055,9 @@ impl TransactionHasher for InvokeTransactionV3 {
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord, From)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum InvokeTransaction {
     V0(InvokeTransactionV0),
     V1(InvokeTransactionV1),
@@ -485,9 +1076,26 @@ macro_rules! implement_invoke_tx_getters {
     };
 }
 
+macro_rules! implement_invoke_tx_ref_getters {
+    ($(($getter:ident, $field:ident, $field_type:ty)),*) => {
+        $(pub fn $getter(&self) -> &$field_type {
+            match self {
+                Self::V0(tx) => &tx.$field,
+                Self::V1(tx) => &tx.$field,
+                Self::V3(tx) => &tx.$field,
+            }
+        })*
+    };
+}
+
 impl InvokeTransaction {
     implement_invoke_tx_getters!((calldata, Calldata), (signature, TransactionSignature));
 
+    implement_invoke_tx_ref_getters!(
+        (calldata_ref, calldata, Calldata),
+        (signature_ref, signature, TransactionSignature)
+    );
+
     pub fn nonce(&self) -> Nonce {
         match self {
             Self::V0(_) => Nonce::default(),
@@ -504,6 +1112,14 @@ impl InvokeTransaction {
         }
     }
 
+    pub fn sender_address_ref(&self) -> &ContractAddress {
+        match self {
+            Self::V0(tx) => &tx.contract_address,
+            Self::V1(tx) => &tx.sender_address,
+            Self::V3(tx) => &tx.sender_address,
+        }
+    }
+
     pub fn version(&self) -> TransactionVersion {
         match self {
             InvokeTransaction::V0(_) => TransactionVersion::ZERO,
@@ -511,6 +1127,22 @@ impl InvokeTransaction {
             InvokeTransaction::V3(_) => TransactionVersion::THREE,
         }
     }
+
+    /// The max fee of the transaction, or `None` for V3 transactions, which have resource bounds
+    /// instead.
+    pub fn max_fee(&self) -> Option<Fee> {
+        TransactionInfo::max_fee(self)
+    }
+
+    /// The resource bounds of V3 transactions, or `None` for earlier versions.
+    pub fn resource_bounds(&self) -> Option<ResourceBoundsMapping> {
+        TransactionInfo::resource_bounds(self)
+    }
+
+    /// The tip of V3 transactions, or `None` for earlier versions.
+    pub fn tip(&self) -> Option<Tip> {
+        TransactionInfo::tip(self)
+    }
 }
 
 impl TransactionHasher for InvokeTransaction {
@@ -535,6 +1167,9 @@ impl TransactionHasher for InvokeTransaction {
 
 /// An L1 handler transaction.
 #[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct L1HandlerTransaction {
     pub version: TransactionVersion,
     pub nonce: Nonce,
@@ -555,6 +1190,8 @@ impl TransactionHasher for L1HandlerTransaction {
 
 /// A declare transaction output.
 #[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct DeclareTransactionOutput {
     pub actual_fee: Fee,
     pub messages_sent: Vec<MessageToL1>,
@@ -566,6 +1203,8 @@ pub struct DeclareTransactionOutput {
 
 /// A deploy-account transaction output.
 #[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct DeployAccountTransactionOutput {
     pub actual_fee: Fee,
     pub messages_sent: Vec<MessageToL1>,
@@ -578,6 +1217,8 @@ pub struct DeployAccountTransactionOutput {
 
 /// A deploy transaction output.
 #[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct DeployTransactionOutput {
     pub actual_fee: Fee,
     pub messages_sent: Vec<MessageToL1>,
@@ -590,6 +1231,8 @@ pub struct DeployTransactionOutput {
 
 /// An invoke transaction output.
 #[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct InvokeTransactionOutput {
     pub actual_fee: Fee,
     pub messages_sent: Vec<MessageToL1>,
@@ -601,6 +1244,8 @@ pub struct InvokeTransactionOutput {
 
 /// An L1 handler transaction output.
 #[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct L1HandlerTransactionOutput {
     pub actual_fee: Fee,
     pub messages_sent: Vec<MessageToL1>,
@@ -612,6 +1257,7 @@ pub struct L1HandlerTransactionOutput {
 
 /// A transaction receipt.
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct TransactionReceipt {
     pub transaction_hash: TransactionHash,
     pub block_hash: BlockHash,
@@ -620,8 +1266,20 @@ pub struct TransactionReceipt {
     pub output: TransactionOutput,
 }
 
+impl TransactionReceipt {
+    /// Returns the inputs of the receipt to the receipt commitment of its block.
+    pub fn for_commitment(&self) -> ReceiptElement {
+        ReceiptElement {
+            transaction_hash: self.transaction_hash,
+            transaction_output: TransactionOutputForHash::from(&self.output),
+        }
+    }
+}
+
 /// Transaction execution status.
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord, Default)]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "execution_status")]
 pub enum TransactionExecutionStatus {
     #[serde(rename = "SUCCEEDED")]
@@ -633,13 +1291,49 @@ pub enum TransactionExecutionStatus {
     Reverted(RevertedTransactionExecutionStatus),
 }
 
+impl TransactionExecutionStatus {
+    pub fn is_succeeded(&self) -> bool {
+        matches!(self, Self::Succeeded)
+    }
+
+    pub fn is_reverted(&self) -> bool {
+        matches!(self, Self::Reverted(_))
+    }
+
+    /// Returns the revert reason of a reverted transaction, or None if it succeeded.
+    pub fn revert_reason(&self) -> Option<&str> {
+        match self {
+            Self::Succeeded => None,
+            Self::Reverted(status) => Some(&status.revert_reason),
+        }
+    }
+}
+
 /// A reverted transaction execution status.
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct RevertedTransactionExecutionStatus {
-    // TODO: Validate it's an ASCII string.
+    // Not validated on deserialization, to allow reading historical data. New data should be
+    // created with [`RevertedTransactionExecutionStatus::new`].
     pub revert_reason: String,
 }
 
+impl RevertedTransactionExecutionStatus {
+    /// Creates a reverted execution status, validating the revert reason is an ASCII string.
+    pub fn new(revert_reason: String) -> Result<Self, StarknetApiError> {
+        if !revert_reason.is_ascii() {
+            return Err(StarknetApiError::NonAsciiString(revert_reason));
+        }
+        Ok(Self { revert_reason })
+    }
+
+    /// Returns the bytes of the revert reason, as they are hashed into the receipt commitment.
+    pub fn revert_reason_bytes(&self) -> &[u8] {
+        self.revert_reason.as_bytes()
+    }
+}
+
 /// A fee.
 #[derive(
     Debug,
@@ -650,27 +1344,26 @@ pub struct RevertedTransactionExecutionStatus {
     Eq,
     PartialEq,
     Hash,
-    Deserialize,
-    Serialize,
     PartialOrd,
     Ord,
     derive_more::Deref,
 )]
-#[serde(from = "PrefixedBytesAsHex<16_usize>", into = "PrefixedBytesAsHex<16_usize>")]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
 pub struct Fee(pub u128);
 
-impl From<PrefixedBytesAsHex<16_usize>> for Fee {
-    fn from(value: PrefixedBytesAsHex<16_usize>) -> Self {
-        Self(u128::from_be_bytes(value.0))
-    }
-}
+impl_inner_conversions!(Fee: u128);
+impl_hex_serialized_int!(Fee, u128, 16);
+impl_from_dec_or_hex_str!(Fee: u128);
 
-impl From<Fee> for PrefixedBytesAsHex<16_usize> {
-    fn from(fee: Fee) -> Self {
-        Self(fee.0.to_be_bytes())
+impl From<u128> for Fee {
+    fn from(value: u128) -> Self {
+        Self(value)
     }
 }
 
+impl_from_through_intermediate!(u128, Fee, u8, u16, u32, u64);
+
 impl From<Fee> for Felt {
     fn from(fee: Fee) -> Self {
         Self::from(fee.0)
@@ -694,6 +1387,9 @@ impl From<Fee> for Felt {
 )]
 pub struct TransactionHash(pub StarkHash);
 
+impl_inner_conversions!(TransactionHash: StarkHash);
+impl_from_hex_str!(TransactionHash: |felt| Ok(TransactionHash(felt)));
+
 impl Display for TransactionHash {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.0)
@@ -704,10 +1400,14 @@ impl Display for TransactionHash {
 #[derive(
     Debug, Copy, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord,
 )]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ContractAddressSalt(pub StarkHash);
 
+impl_hex_display!(ContractAddressSalt);
+
 /// A transaction signature.
 #[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct TransactionSignature(pub Vec<Felt>);
 
 /// A transaction version.
@@ -725,8 +1425,12 @@ pub struct TransactionSignature(pub Vec<Felt>);
     Ord,
     derive_more::Deref,
 )]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct TransactionVersion(pub Felt);
 
+impl_inner_conversions!(TransactionVersion: Felt);
+impl_hex_display!(TransactionVersion);
+
 impl TransactionVersion {
     /// [TransactionVersion] constant that's equal to 0.
     pub const ZERO: Self = { Self(Felt::ZERO) };
@@ -743,6 +1447,7 @@ impl TransactionVersion {
 
 /// The calldata of a transaction.
 #[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Calldata(pub Arc<Vec<Felt>>);
 
 #[macro_export]
@@ -754,6 +1459,7 @@ macro_rules! calldata {
 
 /// An L1 to L2 message.
 #[derive(Debug, Default, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct MessageToL2 {
     pub from_address: EthAddress,
     pub payload: L1ToL2Payload,
@@ -761,6 +1467,8 @@ pub struct MessageToL2 {
 
 /// An L2 to L1 message.
 #[derive(Debug, Default, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct MessageToL1 {
     pub from_address: ContractAddress,
     pub to_address: EthAddress,
@@ -776,7 +1484,12 @@ pub struct L1ToL2Payload(pub Vec<Felt>);
 pub struct L2ToL1Payload(pub Vec<Felt>);
 
 /// An event.
+///
+/// Events are ordered by their emitting address, then by their keys and then by their data, where
+/// keys and data are compared lexicographically, i.e., the order of [`Event::sort_key`].
 #[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Event {
     // TODO: Add a TransactionHash element to this struct, and then remove EventLeafElements.
     pub from_address: ContractAddress,
@@ -784,17 +1497,87 @@ pub struct Event {
     pub content: EventContent,
 }
 
+impl Event {
+    /// Returns the key by which events are ordered: the emitting address, the keys and the data.
+    pub fn sort_key(&self) -> (&ContractAddress, &[EventKey], &[Felt]) {
+        (&self.from_address, &self.content.keys, &self.content.data.0)
+    }
+
+    /// Returns whether the event matches an RPC events filter: the event is emitted by
+    /// `from_address`, if given, and its keys match `keys_pattern` (see
+    /// [EventContent::matches_filter]).
+    pub fn matches(
+        &self,
+        from_address: Option<&ContractAddress>,
+        keys_pattern: &[Vec<EventKey>],
+    ) -> bool {
+        (from_address.is_none() || from_address == Some(&self.from_address))
+            && self.content.matches_filter(keys_pattern)
+    }
+
+    /// Returns a hash of the content of the event, i.e., its emitting address, keys and data:
+    /// Poseidon(from_address, num_keys, key0, key1, ..., num_data, data0, data1, ...).
+    ///
+    /// Unlike the leaf hash of the event commitment, the hash doesn't include the hash of the
+    /// emitting transaction, so identical events of different transactions have the same hash.
+    pub fn content_hash(&self) -> Felt {
+        let keys = self.content.keys.iter().map(|key| key.0).collect::<Vec<_>>();
+        HashChain::new()
+            .chain(self.from_address.0.key())
+            .chain_size_and_elements(&keys)
+            .chain_size_and_elements(&self.content.data.0)
+            .get_poseidon_hash()
+    }
+}
+
 /// An event content.
 #[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct EventContent {
     pub keys: Vec<EventKey>,
     pub data: EventData,
 }
 
+impl EventContent {
+    /// Validates the number of keys and data elements of the event are within the given limits,
+    /// e.g., the limits of an RPC endpoint.
+    pub fn validate_sizes(&self, max_keys: usize, max_data: usize) -> Result<(), StarknetApiError> {
+        if self.keys.len() > max_keys {
+            return Err(StarknetApiError::EventSizeExceeded {
+                field: "keys",
+                size: self.keys.len(),
+                max_size: max_keys,
+            });
+        }
+        if self.data.0.len() > max_data {
+            return Err(StarknetApiError::EventSizeExceeded {
+                field: "data",
+                size: self.data.0.len(),
+                max_size: max_data,
+            });
+        }
+        Ok(())
+    }
+
+    /// Returns whether the keys match the keys pattern of an RPC events filter. The pattern holds
+    /// the allowed keys per position, where an empty set of keys matches any key. Keys beyond the
+    /// pattern are not filtered, but a non-empty position beyond the keys of the event doesn't
+    /// match.
+    pub fn matches_filter(&self, keys_pattern: &[Vec<EventKey>]) -> bool {
+        keys_pattern.iter().enumerate().all(|(position, allowed_keys)| {
+            allowed_keys.is_empty()
+                || self.keys.get(position).is_some_and(|key| allowed_keys.contains(key))
+        })
+    }
+}
+
 /// An event key.
 #[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
 pub struct EventKey(pub Felt);
 
+impl_hex_display!(EventKey);
+
 /// An event data.
 #[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
 pub struct EventData(pub Vec<Felt>);
@@ -803,44 +1586,102 @@ pub struct EventData(pub Vec<Felt>);
 #[derive(
     Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord,
 )]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct TransactionOffsetInBlock(pub usize);
 
 /// The index of an event in [TransactionOutput](`crate::transaction::TransactionOutput`).
 #[derive(
     Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord,
 )]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct EventIndexInTransactionOutput(pub usize);
 
+/// The position of an event in the chain, e.g., a cursor for paginating events.
+///
+/// Ordered by the block, then the transaction and then the event. Serialized as a compact token
+/// of the form `"{block_number}-{transaction_offset}-{event_index}"`.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct EventIndex {
+    pub block_number: BlockNumber,
+    pub transaction_offset: TransactionOffsetInBlock,
+    pub event_index: EventIndexInTransactionOutput,
+}
+
+impl Display for EventIndex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}-{}", self.block_number.0, self.transaction_offset.0, self.event_index.0)
+    }
+}
+
+impl std::str::FromStr for EventIndex {
+    type Err = StarknetApiError;
+
+    fn from_str(token: &str) -> Result<Self, Self::Err> {
+        let invalid_token = || StarknetApiError::InvalidEventIndex(token.to_owned());
+        let mut parts = token.split('-');
+        let mut next_part = || parts.next().ok_or_else(invalid_token);
+        let block_number = next_part()?.parse().map_err(|_| invalid_token())?;
+        let transaction_offset = next_part()?.parse().map_err(|_| invalid_token())?;
+        let event_index = next_part()?.parse().map_err(|_| invalid_token())?;
+        if parts.next().is_some() {
+            return Err(invalid_token());
+        }
+        Ok(EventIndex {
+            block_number: BlockNumber(block_number),
+            transaction_offset: TransactionOffsetInBlock(transaction_offset),
+            event_index: EventIndexInTransactionOutput(event_index),
+        })
+    }
+}
+
+impl Serialize for EventIndex {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for EventIndex {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let token = String::deserialize(deserializer)?;
+        token.parse().map_err(serde::de::Error::custom)
+    }
+}
+
 /// Transaction fee tip.
 #[derive(
     Clone,
     Copy,
     Debug,
     Default,
-    Deserialize,
     Eq,
     Hash,
     Ord,
     PartialEq,
     PartialOrd,
-    Serialize,
     derive_more::Deref,
 )]
-#[serde(from = "PrefixedBytesAsHex<8_usize>", into = "PrefixedBytesAsHex<8_usize>")]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
 pub struct Tip(pub u64);
 
-impl From<PrefixedBytesAsHex<8_usize>> for Tip {
-    fn from(value: PrefixedBytesAsHex<8_usize>) -> Self {
-        Self(u64::from_be_bytes(value.0))
-    }
-}
+impl_inner_conversions!(Tip: u64);
+impl_hex_serialized_int!(Tip, u64, 8);
+impl_from_dec_or_hex_str!(Tip: u64);
 
-impl From<Tip> for PrefixedBytesAsHex<8_usize> {
-    fn from(tip: Tip) -> Self {
-        Self(tip.0.to_be_bytes())
+impl From<u64> for Tip {
+    fn from(value: u64) -> Self {
+        Self(value)
     }
 }
 
+impl_from_through_intermediate!(u64, Tip, u8, u16, u32);
+
 impl From<Tip> for Felt {
     fn from(tip: Tip) -> Self {
         Self::from(tip.0)
@@ -851,6 +1692,9 @@ impl From<Tip> for Felt {
 #[derive(
     Clone, Copy, Debug, Deserialize, EnumIter, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize,
 )]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum Resource {
     #[serde(rename = "L1_GAS")]
     L1Gas,
@@ -858,19 +1702,70 @@ pub enum Resource {
     L2Gas,
 }
 
+/// An amount of units of an execution resource.
+#[derive(
+    Clone, Copy, Debug, Default, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize,
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+pub struct ResourceAmount(
+    #[serde(serialize_with = "u64_to_hex", deserialize_with = "hex_to_u64")] pub u64,
+);
+
+impl ResourceAmount {
+    /// Returns the fee of this amount at the given price per unit, or `None` on overflow.
+    pub fn checked_mul(self, price: ResourcePrice) -> Option<Fee> {
+        price.0.checked_mul(u128::from(self.0)).map(Fee)
+    }
+}
+
+impl From<u64> for ResourceAmount {
+    fn from(value: u64) -> Self {
+        Self(value)
+    }
+}
+
+impl From<ResourceAmount> for u64 {
+    fn from(amount: ResourceAmount) -> Self {
+        amount.0
+    }
+}
+
+/// The price of a single unit of an execution resource.
+#[derive(
+    Clone, Copy, Debug, Default, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize,
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+pub struct ResourcePrice(
+    #[serde(serialize_with = "u128_to_hex", deserialize_with = "hex_to_u128")] pub u128,
+);
+
+impl From<u128> for ResourcePrice {
+    fn from(value: u128) -> Self {
+        Self(value)
+    }
+}
+
+impl From<ResourcePrice> for u128 {
+    fn from(price: ResourcePrice) -> Self {
+        price.0
+    }
+}
+
 /// Fee bounds for an execution resource.
-/// TODO(Yael): add types ResourceAmount and ResourcePrice and use them instead of u64 and u128.
 #[derive(
     Clone, Copy, Debug, Default, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize,
 )]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ResourceBounds {
     // Specifies the maximum amount of each resource allowed for usage during the execution.
-    #[serde(serialize_with = "u64_to_hex", deserialize_with = "hex_to_u64")]
-    pub max_amount: u64,
+    pub max_amount: ResourceAmount,
 
     // Specifies the maximum price the user is willing to pay for each resource unit.
-    #[serde(serialize_with = "u128_to_hex", deserialize_with = "hex_to_u128")]
-    pub max_price_per_unit: u128,
+    pub max_price_per_unit: ResourcePrice,
 }
 
 fn u64_to_hex<S>(value: &u64, serializer: S) -> Result<S::Ok, S::Error>
@@ -905,8 +1800,34 @@ where
 
 /// A mapping from execution resources to their corresponding fee bounds..
 #[derive(Clone, Debug, Default, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ResourceBoundsMapping(pub BTreeMap<Resource, ResourceBounds>);
 
+impl ResourceBoundsMapping {
+    /// Collects the bounds of each resource from an iterator, e.g., the end of a map/filter
+    /// pipeline. Fails unless the iterator yields exactly one bound for each [Resource].
+    pub fn try_from_iter<I>(iter: I) -> Result<Self, StarknetApiError>
+    where
+        I: IntoIterator<Item = (Resource, ResourceBounds)>,
+    {
+        Self::try_from(iter.into_iter().collect::<Vec<_>>())
+    }
+
+    /// Returns the maximal fee that can be charged under these bounds, in a block with the given L1
+    /// gas price, or `None` on overflow. The L1 gas is charged at the block's price, which can't
+    /// exceed its max price per unit.
+    pub fn max_possible_fee(&self, l1_gas_price: GasPrice) -> Option<Fee> {
+        self.0.iter().try_fold(Fee::default(), |total, (resource, bounds)| {
+            let price = match resource {
+                Resource::L1Gas => ResourcePrice(bounds.max_price_per_unit.0.min(l1_gas_price.0)),
+                Resource::L2Gas => bounds.max_price_per_unit,
+            };
+            Some(Fee(total.0.checked_add(bounds.max_amount.checked_mul(price)?.0)?))
+        })
+    }
+}
+
 impl TryFrom<Vec<(Resource, ResourceBounds)>> for ResourceBoundsMapping {
     type Error = StarknetApiError;
     fn try_from(
@@ -930,52 +1851,162 @@ impl TryFrom<Vec<(Resource, ResourceBounds)>> for ResourceBoundsMapping {
 
 /// Paymaster-related data.
 #[derive(Clone, Debug, Default, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct PaymasterData(pub Vec<Felt>);
 
 /// If nonempty, will contain the required data for deploying and initializing an account contract:
 /// its class hash, address salt and constructor calldata.
 #[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Deserialize, Serialize, PartialOrd, Ord)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct AccountDeploymentData(pub Vec<Felt>);
 
 #[derive(Debug, Default, Deserialize, Serialize, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct GasVector {
     pub l1_gas: u64,
     pub l1_data_gas: u64,
 }
 
+impl GasVector {
+    /// Returns the sum of the two gas vectors, or None in case of an overflow.
+    pub fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(Self {
+            l1_gas: self.l1_gas.checked_add(other.l1_gas)?,
+            l1_data_gas: self.l1_data_gas.checked_add(other.l1_data_gas)?,
+        })
+    }
+}
+
 /// The execution resources used by a transaction.
 #[derive(Debug, Default, Deserialize, Serialize, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ExecutionResources {
     pub steps: u64,
+    #[serde(serialize_with = "serialize_builtins_sorted")]
     pub builtin_instance_counter: HashMap<Builtin, u64>,
     pub memory_holes: u64,
     pub da_gas_consumed: GasVector,
     pub gas_consumed: GasVector,
 }
 
-#[derive(Clone, Debug, Deserialize, EnumIter, Eq, Hash, PartialEq, Serialize)]
+// Serialize the builtins in a canonical order, so that the output is deterministic.
+fn serialize_builtins_sorted<S>(
+    builtin_instance_counter: &HashMap<Builtin, u64>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.collect_map(Builtin::iter().filter_map(|builtin| {
+        builtin_instance_counter.get(&builtin).map(|count| (builtin, count))
+    }))
+}
+
+impl ExecutionResources {
+    /// Returns the number of instances of the given builtin, or 0 if the builtin wasn't used.
+    pub fn builtin_count(&self, builtin: Builtin) -> u64 {
+        self.builtin_instance_counter.get(&builtin).copied().unwrap_or_default()
+    }
+
+    /// Returns the total number of builtin instances, over all builtins. Saturates at
+    /// [`u64::MAX`].
+    pub fn total_builtin_applications(&self) -> u64 {
+        self.builtin_instance_counter.values().fold(0, |total, count| total.saturating_add(*count))
+    }
+
+    /// Returns the builtin instance counters in the canonical order of [`Builtin::iter`].
+    pub fn sorted_builtin_instance_counter(&self) -> Vec<(Builtin, u64)> {
+        Builtin::iter()
+            .filter_map(|builtin| {
+                self.builtin_instance_counter.get(&builtin).map(|count| (builtin, *count))
+            })
+            .collect()
+    }
+
+    /// Returns the sum of the two execution resources, or None in case of an overflow.
+    /// The builtin counters are merged, summing the counts of builtins that appear in both.
+    pub fn checked_add(&self, other: &Self) -> Option<Self> {
+        let mut builtin_instance_counter = self.builtin_instance_counter.clone();
+        for (builtin, count) in &other.builtin_instance_counter {
+            let total_count = builtin_instance_counter.entry(builtin.clone()).or_default();
+            *total_count = total_count.checked_add(*count)?;
+        }
+        Some(Self {
+            steps: self.steps.checked_add(other.steps)?,
+            builtin_instance_counter,
+            memory_holes: self.memory_holes.checked_add(other.memory_holes)?,
+            da_gas_consumed: self.da_gas_consumed.checked_add(&other.da_gas_consumed)?,
+            gas_consumed: self.gas_consumed.checked_add(&other.gas_consumed)?,
+        })
+    }
+}
+
+impl Add for ExecutionResources {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.checked_add(&rhs).expect("Execution resources addition overflowed.")
+    }
+}
+
+impl AddAssign for ExecutionResources {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = self.checked_add(&rhs).expect("Execution resources addition overflowed.");
+    }
+}
+
+// The strum names are the serde names, see [Builtin::serde_name].
+#[derive(
+    Clone,
+    Debug,
+    Deserialize,
+    EnumIter,
+    Eq,
+    Hash,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    Serialize,
+    strum_macros::EnumString,
+    strum_macros::IntoStaticStr,
+)]
+#[cfg_attr(feature = "borsh", derive(borsh::BorshSerialize, borsh::BorshDeserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum Builtin {
     #[serde(rename = "range_check_builtin_applications")]
+    #[strum(serialize = "range_check_builtin_applications")]
     RangeCheck,
     #[serde(rename = "pedersen_builtin_applications")]
+    #[strum(serialize = "pedersen_builtin_applications")]
     Pedersen,
     #[serde(rename = "poseidon_builtin_applications")]
+    #[strum(serialize = "poseidon_builtin_applications")]
     Poseidon,
     #[serde(rename = "ec_op_builtin_applications")]
+    #[strum(serialize = "ec_op_builtin_applications")]
     EcOp,
     #[serde(rename = "ecdsa_builtin_applications")]
+    #[strum(serialize = "ecdsa_builtin_applications")]
     Ecdsa,
     #[serde(rename = "bitwise_builtin_applications")]
+    #[strum(serialize = "bitwise_builtin_applications")]
     Bitwise,
     #[serde(rename = "keccak_builtin_applications")]
+    #[strum(serialize = "keccak_builtin_applications")]
     Keccak,
     #[serde(rename = "segment_arena_builtin")]
+    #[strum(serialize = "segment_arena_builtin")]
     SegmentArena,
     #[serde(rename = "add_mod_builtin")]
+    #[strum(serialize = "add_mod_builtin")]
     AddMod,
     #[serde(rename = "mul_mod_builtin")]
+    #[strum(serialize = "mul_mod_builtin")]
     MulMod,
     #[serde(rename = "range_check96_builtin")]
+    #[strum(serialize = "range_check96_builtin")]
     RangeCheck96,
 }
 
@@ -992,6 +2023,11 @@ const MUL_MOD_BUILTIN_NAME: &str = "mul_mod";
 const RANGE_CHECK96_BUILTIN_NAME: &str = "range_check96";
 
 impl Builtin {
+    /// Returns the builtin with the given name (see [`Builtin::name`]), if exists.
+    pub fn from_name(name: &str) -> Option<Builtin> {
+        Self::iter().find(|builtin| builtin.name() == name)
+    }
+
     pub fn name(&self) -> &'static str {
         match self {
             Builtin::RangeCheck => RANGE_CHACK_BUILTIN_NAME,
@@ -1007,4 +2043,10 @@ impl Builtin {
             Builtin::RangeCheck96 => RANGE_CHECK96_BUILTIN_NAME,
         }
     }
+    /// Returns the name of the builtin as it appears in the serialized
+    /// [ExecutionResources](`crate::transaction::ExecutionResources`), e.g.,
+    /// `range_check_builtin_applications`. Parsed back by [FromStr](`std::str::FromStr`).
+    pub fn serde_name(&self) -> &'static str {
+        self.into()
+    }
 }
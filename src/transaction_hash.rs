@@ -1,8 +1,12 @@
+#[cfg(test)]
+#[path = "transaction_hash_test.rs"]
+mod transaction_hash_test;
+
 use once_cell::sync::Lazy;
 use starknet_types_core::felt::Felt;
 
 use crate::block::BlockNumber;
-use crate::core::{calculate_contract_address, ChainId, ContractAddress};
+use crate::core::{encode_shortstring, ChainId};
 use crate::crypto::utils::HashChain;
 use crate::data_availability::DataAvailabilityMode;
 use crate::transaction::{
@@ -21,18 +25,22 @@ const L1_GAS: &ResourceName = b"\0L1_GAS";
 const L2_GAS: &ResourceName = b"\0L2_GAS";
 
 static DECLARE: Lazy<Felt> =
-    Lazy::new(|| ascii_as_felt("declare").expect("ascii_as_felt failed for 'declare'"));
+    Lazy::new(|| encode_shortstring("declare").expect("encode_shortstring failed for 'declare'"));
 static DEPLOY: Lazy<Felt> =
-    Lazy::new(|| ascii_as_felt("deploy").expect("ascii_as_felt failed for 'deploy'"));
+    Lazy::new(|| encode_shortstring("deploy").expect("encode_shortstring failed for 'deploy'"));
 static DEPLOY_ACCOUNT: Lazy<Felt> = Lazy::new(|| {
-    ascii_as_felt("deploy_account").expect("ascii_as_felt failed for 'deploy_account'")
+    encode_shortstring("deploy_account").expect("encode_shortstring failed for 'deploy_account'")
 });
 static INVOKE: Lazy<Felt> =
-    Lazy::new(|| ascii_as_felt("invoke").expect("ascii_as_felt failed for 'invoke'"));
-static L1_HANDLER: Lazy<Felt> =
-    Lazy::new(|| ascii_as_felt("l1_handler").expect("ascii_as_felt failed for 'l1_handler'"));
+    Lazy::new(|| encode_shortstring("invoke").expect("encode_shortstring failed for 'invoke'"));
+static L1_HANDLER: Lazy<Felt> = Lazy::new(|| {
+    encode_shortstring("l1_handler").expect("encode_shortstring failed for 'l1_handler'")
+});
 const CONSTRUCTOR_ENTRY_POINT_SELECTOR: Felt =
     Felt::from_hex_unchecked("0x28ffe4ff0f226a9107253e17a904099aa4f63a02a5621de0576e5aa71bc5194");
+// The only_query bit (bit 128) that is set in the version of transactions sent for simulation.
+pub(crate) const QUERY_VERSION_BASE: Felt =
+    Felt::from_hex_unchecked("0x100000000000000000000000000000000");
 
 /// Calculates hash of a Starknet transaction.
 pub fn get_transaction_hash(
@@ -91,9 +99,60 @@ pub fn get_transaction_hash(
     }
 }
 
-// On mainnet, from this block number onwards, there are no deprecated transactions,
-// enabling us to validate against a single hash calculation.
-const MAINNET_TRANSACTION_HASH_WITH_VERSION: BlockNumber = BlockNumber(1470);
+/// Calculates the hash of a Starknet transaction sent for simulation or fee estimation only, i.e.,
+/// with the only_query bit set in its version.
+pub fn calculate_query_transaction_hash(
+    transaction: &Transaction,
+    chain_id: &ChainId,
+) -> Result<TransactionHash, StarknetApiError> {
+    let query_version = TransactionVersion(QUERY_VERSION_BASE + transaction.version().0);
+    get_transaction_hash(transaction, chain_id, &query_version)
+}
+
+/// A function that calculates the hash of a transaction.
+pub type TransactionHashFn =
+    fn(&Transaction, &ChainId, &TransactionVersion) -> Result<TransactionHash, StarknetApiError>;
+
+/// A scheme of transaction hash calculation, to be selected at runtime, e.g., by an indexer of
+/// several networks that calculate transaction hashes differently.
+#[derive(Debug, Clone, Copy, Default)]
+pub enum HashScheme {
+    /// The Starknet transaction hash, as calculated by [get_transaction_hash].
+    #[default]
+    Starknet,
+    /// A custom calculation, for networks that deviate from Starknet.
+    Custom(TransactionHashFn),
+}
+
+impl HashScheme {
+    /// Calculates the hash of the transaction according to the scheme.
+    pub fn calculate_transaction_hash(
+        &self,
+        transaction: &Transaction,
+        chain_id: &ChainId,
+        transaction_version: &TransactionVersion,
+    ) -> Result<TransactionHash, StarknetApiError> {
+        match self {
+            HashScheme::Starknet => {
+                get_transaction_hash(transaction, chain_id, transaction_version)
+            }
+            HashScheme::Custom(hash_fn) => hash_fn(transaction, chain_id, transaction_version),
+        }
+    }
+}
+
+/// The last mainnet block that may contain transactions with a deprecated hash. After it, a
+/// transaction hash can be validated against a single hash calculation.
+pub const MAINNET_TRANSACTION_HASH_WITH_VERSION: BlockNumber = BlockNumber(1470);
+
+/// Returns the last block of the given chain that may contain transactions with a deprecated
+/// hash, or `None` if deprecated hashes are possible at any height.
+pub fn deprecated_hash_cutoff(chain_id: &ChainId) -> Option<BlockNumber> {
+    match chain_id {
+        ChainId::Mainnet => Some(MAINNET_TRANSACTION_HASH_WITH_VERSION),
+        _ => None,
+    }
+}
 
 // Calculates a list of deprecated hashes for a transaction.
 fn get_deprecated_transaction_hashes(
@@ -102,7 +161,8 @@ fn get_deprecated_transaction_hashes(
     transaction: &Transaction,
     transaction_version: &TransactionVersion,
 ) -> Result<Vec<TransactionHash>, StarknetApiError> {
-    Ok(if chain_id == &ChainId::Mainnet && block_number > &MAINNET_TRANSACTION_HASH_WITH_VERSION {
+    let past_cutoff = deprecated_hash_cutoff(chain_id).is_some_and(|cutoff| block_number > &cutoff);
+    Ok(if past_cutoff {
         vec![]
     } else {
         match transaction {
@@ -130,32 +190,51 @@ fn get_deprecated_transaction_hashes(
     })
 }
 
-/// Validates the hash of a starknet transaction.
-/// For transactions on testnet or those with a low block_number, we validate the
-/// transaction hash against all potential historical hash computations. For recent
-/// transactions on mainnet, the hash is validated by calculating the precise hash
-/// based on the transaction version.
-pub fn validate_transaction_hash(
+/// Returns the hashes a starknet transaction may have, starting with its current hash.
+/// For transactions on testnet or those with a low block_number, the list includes all
+/// potential historical hash computations. For recent transactions on mainnet, the only
+/// possible hash is the precise hash based on the transaction version.
+pub fn possible_transaction_hashes(
     transaction: &Transaction,
     block_number: &BlockNumber,
     chain_id: &ChainId,
-    expected_hash: TransactionHash,
     transaction_version: &TransactionVersion,
-) -> Result<bool, StarknetApiError> {
-    let mut possible_hashes = get_deprecated_transaction_hashes(
+) -> Result<Vec<TransactionHash>, StarknetApiError> {
+    let mut possible_hashes =
+        vec![get_transaction_hash(transaction, chain_id, transaction_version)?];
+    possible_hashes.extend(get_deprecated_transaction_hashes(
         chain_id,
         block_number,
         transaction,
         transaction_version,
-    )?;
-    possible_hashes.push(get_transaction_hash(transaction, chain_id, transaction_version)?);
+    )?);
+    Ok(possible_hashes)
+}
+
+/// Validates the hash of a starknet transaction against its
+/// [possible hashes](`possible_transaction_hashes`).
+pub fn validate_transaction_hash(
+    transaction: &Transaction,
+    block_number: &BlockNumber,
+    chain_id: &ChainId,
+    expected_hash: TransactionHash,
+    transaction_version: &TransactionVersion,
+) -> Result<bool, StarknetApiError> {
+    let possible_hashes =
+        possible_transaction_hashes(transaction, block_number, chain_id, transaction_version)?;
     Ok(possible_hashes.contains(&expected_hash))
 }
 
-// TODO: should be part of core::Felt
-pub(crate) fn ascii_as_felt(ascii_str: &str) -> Result<Felt, StarknetApiError> {
-    Felt::from_hex(hex::encode(ascii_str).as_str())
-        .map_err(|_| StarknetApiError::OutOfRange { string: ascii_str.to_string() })
+/// Validates the hash of a recent starknet transaction, i.e., one that can't have a deprecated
+/// hash, against its current hash. The hash version is taken from the transaction itself.
+///
+/// To validate historical transactions, use [validate_transaction_hash].
+pub fn validate_transaction_hash_at(
+    transaction: &Transaction,
+    chain_id: &ChainId,
+    expected_hash: TransactionHash,
+) -> Result<bool, StarknetApiError> {
+    Ok(get_transaction_hash(transaction, chain_id, &transaction.version())? == expected_hash)
 }
 
 // An implementation of the SNIP: https://github.com/EvyatarO/SNIPs/blob/snip-8/SNIPS/snip-8.md
@@ -185,31 +264,23 @@ fn get_concat_resource(
     resource_bounds: &ResourceBounds,
     resource_name: &ResourceName,
 ) -> Result<Felt, StarknetApiError> {
-    let max_amount = resource_bounds.max_amount.to_be_bytes();
-    let max_price = resource_bounds.max_price_per_unit.to_be_bytes();
+    let max_amount = resource_bounds.max_amount.0.to_be_bytes();
+    let max_price = resource_bounds.max_price_per_unit.0.to_be_bytes();
     let concat_bytes =
         [[0_u8].as_slice(), resource_name.as_slice(), max_amount.as_slice(), max_price.as_slice()]
             .concat();
     Ok(Felt::from_bytes_be(&concat_bytes.try_into().expect("Expect 32 bytes")))
 }
 
-// Receives nonce_mode and fee_mode and returns:
-// [0...0 (192 bit) | nonce_mode (32 bit) | fee_mode (32 bit)].
-// An implementation of the SNIP: https://github.com/EvyatarO/SNIPs/blob/snip-8/SNIPS/snip-8.md.
-fn concat_data_availability_mode(
+/// Packs the data availability modes of a V3 transaction into a felt, as hashed:
+/// [0...0 (192 bit) | nonce_mode (32 bit) | fee_mode (32 bit)].
+///
+/// An implementation of the SNIP: https://github.com/EvyatarO/SNIPs/blob/snip-8/SNIPS/snip-8.md.
+pub fn concat_data_availability_mode(
     nonce_mode: &DataAvailabilityMode,
     fee_mode: &DataAvailabilityMode,
 ) -> Felt {
-    (data_availability_mode_index(fee_mode)
-        + (data_availability_mode_index(nonce_mode) << DATA_AVAILABILITY_MODE_BITS))
-        .into()
-}
-
-fn data_availability_mode_index(mode: &DataAvailabilityMode) -> u64 {
-    match mode {
-        DataAvailabilityMode::L1 => 0,
-        DataAvailabilityMode::L2 => 1,
-    }
+    (fee_mode.index() + (nonce_mode.index() << DATA_AVAILABILITY_MODE_BITS)).into()
 }
 
 pub(crate) fn get_deploy_transaction_hash(
@@ -234,12 +305,7 @@ fn get_common_deploy_transaction_hash(
     is_deprecated: bool,
     transaction_version: &TransactionVersion,
 ) -> Result<TransactionHash, StarknetApiError> {
-    let contract_address = calculate_contract_address(
-        transaction.contract_address_salt,
-        transaction.class_hash,
-        &transaction.constructor_calldata,
-        ContractAddress::from(0_u8),
-    )?;
+    let contract_address = transaction.calculate_contract_address()?;
 
     Ok(TransactionHash(
         HashChain::new()
@@ -266,7 +332,7 @@ fn get_common_deploy_transaction_hash(
                 None
             }
         })
-        .chain(&ascii_as_felt(chain_id.to_string().as_str())?)
+        .chain(&encode_shortstring(chain_id.to_string().as_str())?)
         .get_pedersen_hash(),
     ))
 }
@@ -301,7 +367,7 @@ fn get_common_invoke_transaction_v0_hash(
             .chain(&transaction.entry_point_selector.0)
             .chain(&HashChain::new().chain_iter(transaction.calldata.0.iter()).get_pedersen_hash())
             .chain_if_fn(|| if !is_deprecated { Some(transaction.max_fee.0.into()) } else { None })
-            .chain(&ascii_as_felt(chain_id.to_string().as_str())?)
+            .chain(&encode_shortstring(chain_id.to_string().as_str())?)
             .get_pedersen_hash(),
     ))
 }
@@ -319,43 +385,66 @@ pub(crate) fn get_invoke_transaction_v1_hash(
         .chain(&Felt::ZERO) // No entry point selector in invoke transaction.
         .chain(&HashChain::new().chain_iter(transaction.calldata.0.iter()).get_pedersen_hash())
         .chain(&transaction.max_fee.0.into())
-        .chain(&ascii_as_felt(chain_id.to_string().as_str())?)
+        .chain(&encode_shortstring(chain_id.to_string().as_str())?)
         .chain(&transaction.nonce.0)
         .get_pedersen_hash(),
     ))
 }
 
+/// The intermediate values of an invoke V3 transaction hash computation.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct InvokeV3HashComponents {
+    pub tip_resource_bounds_hash: Felt,
+    pub paymaster_data_hash: Felt,
+    pub data_availability_mode: Felt,
+    pub account_deployment_data_hash: Felt,
+    pub calldata_hash: Felt,
+}
+
+/// Calculates the intermediate hashes that compose the hash of an invoke V3 transaction.
+pub fn get_invoke_transaction_v3_hash_components(
+    transaction: &InvokeTransactionV3,
+) -> Result<InvokeV3HashComponents, StarknetApiError> {
+    Ok(InvokeV3HashComponents {
+        tip_resource_bounds_hash: get_tip_resource_bounds_hash(
+            &transaction.resource_bounds,
+            &transaction.tip,
+        )?,
+        paymaster_data_hash: HashChain::new()
+            .chain_iter(transaction.paymaster_data.0.iter())
+            .get_poseidon_hash(),
+        data_availability_mode: concat_data_availability_mode(
+            &transaction.nonce_data_availability_mode,
+            &transaction.fee_data_availability_mode,
+        ),
+        account_deployment_data_hash: HashChain::new()
+            .chain_iter(transaction.account_deployment_data.0.iter())
+            .get_poseidon_hash(),
+        calldata_hash: HashChain::new()
+            .chain_iter(transaction.calldata.0.iter())
+            .get_poseidon_hash(),
+    })
+}
+
 pub(crate) fn get_invoke_transaction_v3_hash(
     transaction: &InvokeTransactionV3,
     chain_id: &ChainId,
     transaction_version: &TransactionVersion,
 ) -> Result<TransactionHash, StarknetApiError> {
-    let tip_resource_bounds_hash =
-        get_tip_resource_bounds_hash(&transaction.resource_bounds, &transaction.tip)?;
-    let paymaster_data_hash =
-        HashChain::new().chain_iter(transaction.paymaster_data.0.iter()).get_poseidon_hash();
-    let data_availability_mode = concat_data_availability_mode(
-        &transaction.nonce_data_availability_mode,
-        &transaction.fee_data_availability_mode,
-    );
-    let account_deployment_data_hash = HashChain::new()
-        .chain_iter(transaction.account_deployment_data.0.iter())
-        .get_poseidon_hash();
-    let calldata_hash =
-        HashChain::new().chain_iter(transaction.calldata.0.iter()).get_poseidon_hash();
+    let components = get_invoke_transaction_v3_hash_components(transaction)?;
 
     Ok(TransactionHash(
         HashChain::new()
             .chain(&INVOKE)
             .chain(&transaction_version.0)
             .chain(transaction.sender_address.0.key())
-            .chain(&tip_resource_bounds_hash)
-            .chain(&paymaster_data_hash)
-            .chain(&ascii_as_felt(chain_id.to_string().as_str())?)
+            .chain(&components.tip_resource_bounds_hash)
+            .chain(&components.paymaster_data_hash)
+            .chain(&encode_shortstring(chain_id.to_string().as_str())?)
             .chain(&transaction.nonce.0)
-            .chain(&data_availability_mode)
-            .chain(&account_deployment_data_hash)
-            .chain(&calldata_hash)
+            .chain(&components.data_availability_mode)
+            .chain(&components.account_deployment_data_hash)
+            .chain(&components.calldata_hash)
             .get_poseidon_hash(),
     ))
 }
@@ -401,6 +490,24 @@ fn get_deprecated_l1_handler_transaction_hashes(
     ])
 }
 
+/// Returns all the hashes an L1 handler transaction may have had over time, starting with the
+/// current one, followed by the deprecated hash schemes (hashed as an invoke, and without the
+/// version and fee).
+pub fn l1_handler_transaction_hash_variants(
+    transaction: &L1HandlerTransaction,
+    chain_id: &ChainId,
+    transaction_version: &TransactionVersion,
+) -> Result<Vec<TransactionHash>, StarknetApiError> {
+    let mut hashes =
+        vec![get_l1_handler_transaction_hash(transaction, chain_id, transaction_version)?];
+    hashes.extend(get_deprecated_l1_handler_transaction_hashes(
+        transaction,
+        chain_id,
+        transaction_version,
+    )?);
+    Ok(hashes)
+}
+
 fn get_common_l1_handler_transaction_hash(
     transaction: &L1HandlerTransaction,
     chain_id: &ChainId,
@@ -434,7 +541,7 @@ fn get_common_l1_handler_transaction_hash(
                 None
             }
         })
-        .chain(&ascii_as_felt(chain_id.to_string().as_str())?)
+        .chain(&encode_shortstring(chain_id.to_string().as_str())?)
         .chain_if_fn(|| {
             if version > L1HandlerVersions::AsInvoke {
                 Some(transaction.nonce.0)
@@ -459,7 +566,7 @@ pub(crate) fn get_declare_transaction_v0_hash(
         .chain(&Felt::ZERO) // No entry point selector in declare transaction.
         .chain(&HashChain::new().get_pedersen_hash())
         .chain(&transaction.max_fee.0.into())
-        .chain(&ascii_as_felt(chain_id.to_string().as_str())?)
+        .chain(&encode_shortstring(chain_id.to_string().as_str())?)
         .chain(&transaction.class_hash.0)
         .get_pedersen_hash(),
     ))
@@ -478,7 +585,7 @@ pub(crate) fn get_declare_transaction_v1_hash(
         .chain(&Felt::ZERO) // No entry point selector in declare transaction.
         .chain(&HashChain::new().chain(&transaction.class_hash.0).get_pedersen_hash())
         .chain(&transaction.max_fee.0.into())
-        .chain(&ascii_as_felt(chain_id.to_string().as_str())?)
+        .chain(&encode_shortstring(chain_id.to_string().as_str())?)
         .chain(&transaction.nonce.0)
         .get_pedersen_hash(),
     ))
@@ -497,7 +604,7 @@ pub(crate) fn get_declare_transaction_v2_hash(
         .chain(&Felt::ZERO) // No entry point selector in declare transaction.
         .chain(&HashChain::new().chain(&transaction.class_hash.0).get_pedersen_hash())
         .chain(&transaction.max_fee.0.into())
-        .chain(&ascii_as_felt(chain_id.to_string().as_str())?)
+        .chain(&encode_shortstring(chain_id.to_string().as_str())?)
         .chain(&transaction.nonce.0)
         .chain(&transaction.compiled_class_hash.0)
         .get_pedersen_hash(),
@@ -528,7 +635,7 @@ pub(crate) fn get_declare_transaction_v3_hash(
             .chain(transaction.sender_address.0.key())
             .chain(&tip_resource_bounds_hash)
             .chain(&paymaster_data_hash)
-            .chain(&ascii_as_felt(chain_id.to_string().as_str())?)
+            .chain(&encode_shortstring(chain_id.to_string().as_str())?)
             .chain(&transaction.nonce.0)
             .chain(&data_availability_mode)
             .chain(&account_deployment_data_hash)
@@ -549,12 +656,7 @@ pub(crate) fn get_deploy_account_transaction_v1_hash(
         .chain_iter(transaction.constructor_calldata.0.iter())
         .get_pedersen_hash();
 
-    let contract_address = calculate_contract_address(
-        transaction.contract_address_salt,
-        transaction.class_hash,
-        &transaction.constructor_calldata,
-        ContractAddress::from(0_u8),
-    )?;
+    let contract_address = transaction.calculate_contract_address()?;
 
     Ok(TransactionHash(
         HashChain::new()
@@ -564,7 +666,7 @@ pub(crate) fn get_deploy_account_transaction_v1_hash(
         .chain(&Felt::ZERO) // No entry point selector in deploy account transaction.
         .chain(&calldata_hash)
         .chain(&transaction.max_fee.0.into())
-        .chain(&ascii_as_felt(chain_id.to_string().as_str())?)
+        .chain(&encode_shortstring(chain_id.to_string().as_str())?)
         .chain(&transaction.nonce.0)
         .get_pedersen_hash(),
     ))
@@ -575,12 +677,7 @@ pub(crate) fn get_deploy_account_transaction_v3_hash(
     chain_id: &ChainId,
     transaction_version: &TransactionVersion,
 ) -> Result<TransactionHash, StarknetApiError> {
-    let contract_address = calculate_contract_address(
-        transaction.contract_address_salt,
-        transaction.class_hash,
-        &transaction.constructor_calldata,
-        ContractAddress::from(0_u8),
-    )?;
+    let contract_address = transaction.calculate_contract_address()?;
     let tip_resource_bounds_hash =
         get_tip_resource_bounds_hash(&transaction.resource_bounds, &transaction.tip)?;
     let paymaster_data_hash =
@@ -599,7 +696,7 @@ pub(crate) fn get_deploy_account_transaction_v3_hash(
             .chain(contract_address.0.key())
             .chain(&tip_resource_bounds_hash)
             .chain(&paymaster_data_hash)
-            .chain(&ascii_as_felt(chain_id.to_string().as_str())?)
+            .chain(&encode_shortstring(chain_id.to_string().as_str())?)
             .chain(&data_availability_mode)
             .chain(&transaction.nonce.0)
             .chain(&constructor_calldata_hash)
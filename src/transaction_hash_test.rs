@@ -0,0 +1,273 @@
+use std::collections::BTreeMap;
+
+use starknet_types_core::felt::Felt;
+use starknet_types_core::hash::{Poseidon, StarkHash as CoreStarkHash};
+
+use super::{
+    calculate_query_transaction_hash, concat_data_availability_mode, deprecated_hash_cutoff,
+    get_invoke_transaction_v3_hash_components, get_l1_handler_transaction_hash,
+    get_transaction_hash, l1_handler_transaction_hash_variants, possible_transaction_hashes,
+    validate_transaction_hash, validate_transaction_hash_at, HashScheme,
+};
+use crate::block::BlockNumber;
+use crate::core::{ChainId, ContractAddress, EntryPointSelector, Nonce, PatriciaKey};
+use crate::data_availability::DataAvailabilityMode;
+use crate::transaction::{
+    AccountDeploymentData, Calldata, Fee, InvokeTransaction, InvokeTransactionV0,
+    InvokeTransactionV3, L1HandlerTransaction, PaymasterData, Resource, ResourceAmount,
+    ResourceBounds, ResourceBoundsMapping, ResourcePrice, Tip, Transaction, TransactionHash,
+    TransactionHasher, TransactionSignature, TransactionVersion,
+};
+use crate::{calldata, contract_address, felt, patricia_key, StarknetApiError};
+
+fn invoke_v3_for_testing() -> InvokeTransactionV3 {
+    InvokeTransactionV3 {
+        resource_bounds: ResourceBoundsMapping(BTreeMap::from([
+            (
+                Resource::L1Gas,
+                ResourceBounds {
+                    max_amount: ResourceAmount(0x186a0),
+                    max_price_per_unit: ResourcePrice(0x5af3107a4000),
+                },
+            ),
+            (Resource::L2Gas, ResourceBounds::default()),
+        ])),
+        tip: Tip(0),
+        signature: TransactionSignature(vec![Felt::ONE]),
+        nonce: Nonce(felt!("0x5")),
+        sender_address: contract_address!("0x123"),
+        calldata: calldata![felt!("0x1"), felt!("0x2"), felt!("0x3")],
+        nonce_data_availability_mode: DataAvailabilityMode::L1,
+        fee_data_availability_mode: DataAvailabilityMode::L2,
+        paymaster_data: PaymasterData(vec![]),
+        account_deployment_data: AccountDeploymentData(vec![felt!("0x7")]),
+    }
+}
+
+#[test]
+fn invoke_v3_hash_matches_spec_encoding() {
+    let transaction = invoke_v3_for_testing();
+    let chain_id = ChainId::Sepolia;
+
+    // The hash preimage, encoded by hand according to SNIP-8.
+    let tip_resource_bounds_hash = Poseidon::hash_array(&[
+        // Tip.
+        Felt::ZERO,
+        // 'L1_GAS' | max_amount | max_price_per_unit.
+        felt!("0x4c315f47415300000000000186a0000000000000000000005af3107a4000"),
+        // 'L2_GAS' | 0 | 0.
+        felt!("0x4c325f474153000000000000000000000000000000000000000000000000"),
+    ]);
+    let components = get_invoke_transaction_v3_hash_components(&transaction).unwrap();
+    assert_eq!(components.tip_resource_bounds_hash, tip_resource_bounds_hash);
+    assert_eq!(components.data_availability_mode, Felt::ONE);
+
+    let expected_hash = TransactionHash(Poseidon::hash_array(&[
+        // 'invoke'.
+        felt!("0x696e766f6b65"),
+        felt!("0x3"),
+        felt!("0x123"),
+        tip_resource_bounds_hash,
+        // Empty paymaster data.
+        Poseidon::hash_array(&[]),
+        // 'SN_SEPOLIA'.
+        felt!("0x534e5f5345504f4c4941"),
+        felt!("0x5"),
+        // Nonce DA mode L1 (0) in the upper bits and fee DA mode L2 (1) in the lower bits.
+        felt!("0x1"),
+        Poseidon::hash_array(&[felt!("0x7")]),
+        Poseidon::hash_array(&[felt!("0x1"), felt!("0x2"), felt!("0x3")]),
+    ]));
+    assert_eq!(
+        transaction.calculate_transaction_hash(&chain_id, &TransactionVersion::THREE).unwrap(),
+        expected_hash
+    );
+}
+
+#[test]
+fn query_transaction_hash() {
+    let transaction = Transaction::Invoke(InvokeTransaction::V3(invoke_v3_for_testing()));
+    let chain_id = ChainId::Sepolia;
+
+    let query_hash = calculate_query_transaction_hash(&transaction, &chain_id).unwrap();
+    let hash = get_transaction_hash(&transaction, &chain_id, &transaction.version()).unwrap();
+    assert_ne!(query_hash, hash);
+    let query_version = TransactionVersion(felt!("0x100000000000000000000000000000003"));
+    assert_eq!(query_hash, get_transaction_hash(&transaction, &chain_id, &query_version).unwrap());
+}
+
+// A hypothetical scheme that doesn't commit to the chain id.
+fn chain_agnostic_hash(
+    transaction: &Transaction,
+    _chain_id: &ChainId,
+    transaction_version: &TransactionVersion,
+) -> Result<TransactionHash, StarknetApiError> {
+    get_transaction_hash(transaction, &ChainId::Other("ANY".to_owned()), transaction_version)
+}
+
+#[test]
+fn hash_scheme_dispatch() {
+    let transaction = Transaction::Invoke(InvokeTransaction::V3(invoke_v3_for_testing()));
+    let version = TransactionVersion::THREE;
+
+    let starknet_hash = HashScheme::default()
+        .calculate_transaction_hash(&transaction, &ChainId::Mainnet, &version)
+        .unwrap();
+    assert_eq!(
+        starknet_hash,
+        get_transaction_hash(&transaction, &ChainId::Mainnet, &version).unwrap()
+    );
+    assert_eq!(
+        starknet_hash,
+        transaction.calculate_transaction_hash(&ChainId::Mainnet, &version).unwrap()
+    );
+
+    let schemes = [HashScheme::Starknet, HashScheme::Custom(chain_agnostic_hash)];
+    let hashes: Vec<_> = schemes
+        .iter()
+        .map(|scheme| {
+            scheme.calculate_transaction_hash(&transaction, &ChainId::Mainnet, &version).unwrap()
+        })
+        .collect();
+    assert_eq!(hashes[0], starknet_hash);
+    assert_ne!(hashes[1], starknet_hash);
+    assert_eq!(
+        hashes[1],
+        HashScheme::Custom(chain_agnostic_hash)
+            .calculate_transaction_hash(&transaction, &ChainId::Sepolia, &version)
+            .unwrap()
+    );
+}
+
+#[test]
+fn possible_transaction_hashes_invoke_v0() {
+    let transaction = Transaction::Invoke(InvokeTransaction::V0(InvokeTransactionV0 {
+        max_fee: Fee(100),
+        signature: TransactionSignature(vec![felt!("0x1")]),
+        contract_address: contract_address!("0x123"),
+        entry_point_selector: EntryPointSelector(felt!("0x456")),
+        calldata: calldata![felt!("0x1"), felt!("0x2")],
+    }));
+    let version = transaction.version();
+    let block_number = BlockNumber(100_000);
+    let canonical_hash = get_transaction_hash(&transaction, &ChainId::Sepolia, &version).unwrap();
+
+    // On testnet, the deprecated invoke V0 hash is possible as well.
+    let hashes =
+        possible_transaction_hashes(&transaction, &block_number, &ChainId::Sepolia, &version)
+            .unwrap();
+    assert_eq!(hashes.len(), 2);
+    assert_eq!(hashes[0], canonical_hash);
+    assert_ne!(hashes[1], canonical_hash);
+    let chain_id = ChainId::Sepolia;
+    for hash in hashes {
+        assert!(
+            validate_transaction_hash(&transaction, &block_number, &chain_id, hash, &version)
+                .unwrap()
+        );
+    }
+
+    // On mainnet, only the canonical hash is possible after the deprecated hashes were dropped.
+    let hashes =
+        possible_transaction_hashes(&transaction, &block_number, &ChainId::Mainnet, &version)
+            .unwrap();
+    let mainnet_hash = get_transaction_hash(&transaction, &ChainId::Mainnet, &version).unwrap();
+    assert_eq!(hashes, vec![mainnet_hash]);
+}
+
+#[test]
+fn l1_handler_hash_variants() {
+    let transaction = L1HandlerTransaction {
+        version: TransactionVersion::ZERO,
+        nonce: Nonce(felt!("0x7")),
+        contract_address: contract_address!("0x123"),
+        entry_point_selector: EntryPointSelector(felt!("0x456")),
+        calldata: calldata![felt!("0x1"), felt!("0x2")],
+    };
+    let chain_id = ChainId::Mainnet;
+    let version = TransactionVersion::ZERO;
+
+    let variants = l1_handler_transaction_hash_variants(&transaction, &chain_id, &version).unwrap();
+    assert_eq!(variants.len(), 3);
+    assert_eq!(
+        variants[0],
+        get_l1_handler_transaction_hash(&transaction, &chain_id, &version).unwrap()
+    );
+    assert_ne!(variants[0], variants[1]);
+    assert_ne!(variants[0], variants[2]);
+    assert_ne!(variants[1], variants[2]);
+
+    // The deprecated variants are exactly the ones accepted for historical L1 handlers.
+    let transaction = Transaction::L1Handler(transaction);
+    let hashes =
+        possible_transaction_hashes(&transaction, &BlockNumber(0), &chain_id, &version).unwrap();
+    assert_eq!(hashes, variants);
+}
+
+#[test]
+fn deprecated_hashes_cutoff_boundary() {
+    let transaction = Transaction::Invoke(InvokeTransaction::V0(InvokeTransactionV0 {
+        contract_address: contract_address!("0x123"),
+        ..Default::default()
+    }));
+    let version = transaction.version();
+    let chain_id = ChainId::Mainnet;
+    let cutoff = deprecated_hash_cutoff(&chain_id).unwrap();
+    assert_eq!(cutoff, BlockNumber(1470));
+    assert_eq!(deprecated_hash_cutoff(&ChainId::Sepolia), None);
+
+    let at_cutoff =
+        possible_transaction_hashes(&transaction, &cutoff, &chain_id, &version).unwrap();
+    assert_eq!(at_cutoff.len(), 2);
+    let after_cutoff =
+        possible_transaction_hashes(&transaction, &cutoff.unchecked_next(), &chain_id, &version)
+            .unwrap();
+    assert_eq!(after_cutoff, vec![at_cutoff[0]]);
+    assert!(
+        !validate_transaction_hash(
+            &transaction,
+            &cutoff.unchecked_next(),
+            &chain_id,
+            at_cutoff[1],
+            &version
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn validate_transaction_hash_overloads() {
+    let transaction = Transaction::Invoke(InvokeTransaction::V0(InvokeTransactionV0 {
+        contract_address: contract_address!("0x123"),
+        ..Default::default()
+    }));
+    let version = transaction.version();
+    let chain_id = ChainId::Sepolia;
+    let hashes =
+        possible_transaction_hashes(&transaction, &BlockNumber(0), &chain_id, &version).unwrap();
+    let (canonical_hash, deprecated_hash) = (hashes[0], hashes[1]);
+
+    // With a block number, historical hashes are accepted.
+    for hash in [canonical_hash, deprecated_hash] {
+        assert!(
+            validate_transaction_hash(&transaction, &BlockNumber(0), &chain_id, hash, &version)
+                .unwrap()
+        );
+    }
+
+    // Without one, the transaction is assumed to be recent and only its current hash is valid.
+    assert!(validate_transaction_hash_at(&transaction, &chain_id, canonical_hash).unwrap());
+    assert!(!validate_transaction_hash_at(&transaction, &chain_id, deprecated_hash).unwrap());
+    assert!(
+        !validate_transaction_hash_at(&transaction, &ChainId::Mainnet, canonical_hash).unwrap()
+    );
+}
+
+#[test]
+fn test_concat_data_availability_mode() {
+    use DataAvailabilityMode::{L1, L2};
+    assert_eq!(concat_data_availability_mode(&L1, &L1), felt!("0x0"));
+    assert_eq!(concat_data_availability_mode(&L1, &L2), felt!("0x1"));
+    assert_eq!(concat_data_availability_mode(&L2, &L1), felt!("0x100000000"));
+    assert_eq!(concat_data_availability_mode(&L2, &L2), felt!("0x100000001"));
+}
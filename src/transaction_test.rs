@@ -0,0 +1,834 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use assert_matches::assert_matches;
+use starknet_types_core::felt::Felt;
+use starknet_types_core::hash::{Poseidon, StarkHash};
+use strum::IntoEnumIterator;
+
+use super::{
+    AccountDeploymentData, Builtin, Calldata, ContractAddressSalt, DeclareTransaction,
+    DeclareTransactionV2, DeployAccountTransaction, DeployAccountTransactionV1,
+    DeployAccountTransactionV3, DeployTransaction, Event, EventContent, EventData, EventIndex,
+    EventIndexInTransactionOutput, EventKey, ExecutionResources, Fee, GasVector, HashedTransaction,
+    InvokeTransaction, InvokeTransactionOutput, InvokeTransactionV0, InvokeTransactionV1,
+    InvokeTransactionV3, L1HandlerTransaction, PaymasterData, Resource, ResourceAmount,
+    ResourceBounds, ResourceBoundsMapping, ResourcePrice, RevertedTransactionExecutionStatus, Tip,
+    Transaction, TransactionExecutionStatus, TransactionHash, TransactionInfo,
+    TransactionOffsetInBlock, TransactionOutput, TransactionSignature, TransactionType,
+    TransactionVersion,
+};
+use crate::block::{BlockNumber, GasPrice};
+use crate::core::{
+    calculate_contract_address, ChainId, ClassHash, ContractAddress, Nonce, PatriciaKey,
+};
+use crate::data_availability::DataAvailabilityMode;
+use crate::transaction_hash::get_transaction_hash;
+use crate::{calldata, class_hash, contract_address, felt, patricia_key, StarknetApiError};
+
+#[test]
+fn builtin_name_round_trip() {
+    assert_eq!(Builtin::iter().count(), 11);
+    for builtin in Builtin::iter() {
+        assert_eq!(Builtin::from_name(builtin.name()), Some(builtin.clone()));
+        assert_eq!(Builtin::from_str(builtin.serde_name()).unwrap(), builtin);
+
+        let serialized = serde_json::to_string(&builtin).unwrap();
+        assert_eq!(serialized, format!("\"{}\"", builtin.serde_name()));
+        assert_eq!(serde_json::from_str::<Builtin>(&serialized).unwrap(), builtin);
+    }
+    assert_eq!(Builtin::RangeCheck.serde_name(), "range_check_builtin_applications");
+}
+
+#[test]
+fn execution_resources_add() {
+    let resources = ExecutionResources {
+        steps: 10,
+        builtin_instance_counter: HashMap::from([(Builtin::Pedersen, 1), (Builtin::RangeCheck, 2)]),
+        memory_holes: 3,
+        da_gas_consumed: GasVector { l1_gas: 4, l1_data_gas: 5 },
+        gas_consumed: GasVector { l1_gas: 6, l1_data_gas: 7 },
+    };
+    let other_resources = ExecutionResources {
+        steps: 20,
+        builtin_instance_counter: HashMap::from([(Builtin::Pedersen, 8), (Builtin::Poseidon, 9)]),
+        memory_holes: 1,
+        da_gas_consumed: GasVector { l1_gas: 1, l1_data_gas: 1 },
+        gas_consumed: GasVector { l1_gas: 1, l1_data_gas: 1 },
+    };
+    let expected_resources = ExecutionResources {
+        steps: 30,
+        builtin_instance_counter: HashMap::from([
+            (Builtin::Pedersen, 9),
+            (Builtin::RangeCheck, 2),
+            (Builtin::Poseidon, 9),
+        ]),
+        memory_holes: 4,
+        da_gas_consumed: GasVector { l1_gas: 5, l1_data_gas: 6 },
+        gas_consumed: GasVector { l1_gas: 7, l1_data_gas: 8 },
+    };
+
+    assert_eq!(resources.checked_add(&other_resources).unwrap(), expected_resources);
+    assert_eq!(resources.clone() + other_resources.clone(), expected_resources);
+    let mut accumulated_resources = resources;
+    accumulated_resources += other_resources;
+    assert_eq!(accumulated_resources, expected_resources);
+}
+
+#[test]
+fn execution_resources_checked_add_overflow() {
+    let resources = ExecutionResources {
+        builtin_instance_counter: HashMap::from([(Builtin::Pedersen, u64::MAX)]),
+        ..Default::default()
+    };
+    let other_resources = ExecutionResources {
+        builtin_instance_counter: HashMap::from([(Builtin::Pedersen, 1)]),
+        ..Default::default()
+    };
+    assert_eq!(resources.checked_add(&other_resources), None);
+
+    let resources = ExecutionResources { steps: u64::MAX, ..Default::default() };
+    let other_resources = ExecutionResources { steps: 1, ..Default::default() };
+    assert_eq!(resources.checked_add(&other_resources), None);
+}
+
+#[test]
+fn declare_transaction_ref_getters() {
+    let tx = DeclareTransaction::V2(DeclareTransactionV2 {
+        signature: TransactionSignature(vec![Felt::ONE, Felt::TWO]),
+        nonce: Nonce(Felt::THREE),
+        class_hash: class_hash!("0x4"),
+        sender_address: contract_address!("0x5"),
+        ..Default::default()
+    });
+    assert_eq!(tx.class_hash_ref(), &tx.class_hash());
+    assert_eq!(tx.nonce_ref(), &tx.nonce());
+    assert_eq!(tx.sender_address_ref(), &tx.sender_address());
+    assert_eq!(tx.signature_ref(), &tx.signature());
+}
+
+#[test]
+fn deploy_account_transaction_ref_getters() {
+    let tx = DeployAccountTransaction::V1(DeployAccountTransactionV1 {
+        signature: TransactionSignature(vec![Felt::ONE]),
+        nonce: Nonce(Felt::TWO),
+        class_hash: class_hash!("0x3"),
+        contract_address_salt: ContractAddressSalt(felt!("0x4")),
+        constructor_calldata: calldata![felt!("0x5"), felt!("0x6")],
+        ..Default::default()
+    });
+    assert_eq!(tx.class_hash_ref(), &tx.class_hash());
+    assert_eq!(tx.constructor_calldata_ref(), &tx.constructor_calldata());
+    assert_eq!(tx.contract_address_salt_ref(), &tx.contract_address_salt());
+    assert_eq!(tx.nonce_ref(), &tx.nonce());
+    assert_eq!(tx.signature_ref(), &tx.signature());
+}
+
+#[test]
+fn invoke_transaction_ref_getters() {
+    let tx = InvokeTransaction::V1(InvokeTransactionV1 {
+        signature: TransactionSignature(vec![Felt::ONE]),
+        nonce: Nonce(Felt::TWO),
+        sender_address: contract_address!("0x3"),
+        calldata: calldata![felt!("0x4")],
+        ..Default::default()
+    });
+    assert_eq!(tx.calldata_ref(), &tx.calldata());
+    assert_eq!(tx.sender_address_ref(), &tx.sender_address());
+    assert_eq!(tx.signature_ref(), &tx.signature());
+}
+
+#[test]
+fn resource_bounds_serde() {
+    let resource_bounds = ResourceBounds {
+        max_amount: ResourceAmount(0x64),
+        max_price_per_unit: ResourcePrice(0xabc),
+    };
+    let serialized = serde_json::to_value(resource_bounds).unwrap();
+    assert_eq!(
+        serialized,
+        serde_json::json!({"max_amount": "0x64", "max_price_per_unit": "0xabc"})
+    );
+    assert_eq!(serde_json::from_value::<ResourceBounds>(serialized).unwrap(), resource_bounds);
+}
+
+#[test]
+fn resource_amount_and_price_fee() {
+    let amount = ResourceAmount::from(3_u64);
+    let price = ResourcePrice::from(7_u128);
+    assert_eq!(u64::from(amount), 3);
+    assert_eq!(u128::from(price), 7);
+    assert_eq!(amount.checked_mul(price), Some(Fee(21)));
+    assert_eq!(ResourceAmount(2).checked_mul(ResourcePrice(u128::MAX)), None);
+}
+
+#[test]
+fn transaction_output_events_by_address() {
+    let event = |address: &str, data: u8| Event {
+        from_address: contract_address!(address),
+        content: EventContent { keys: vec![], data: EventData(vec![Felt::from(data)]) },
+    };
+    let events = vec![event("0x1", 0), event("0x2", 1), event("0x1", 2)];
+    let output = TransactionOutput::Invoke(InvokeTransactionOutput {
+        events: events.clone(),
+        ..Default::default()
+    });
+
+    assert_eq!(output.event_count(), 3);
+    let events_by_address = output.events_by_address();
+    assert_eq!(
+        events_by_address.keys().collect::<Vec<_>>(),
+        vec![&contract_address!("0x1"), &contract_address!("0x2")]
+    );
+    assert_eq!(events_by_address[&contract_address!("0x1")], vec![&events[0], &events[2]]);
+    assert_eq!(events_by_address[&contract_address!("0x2")], vec![&events[1]]);
+}
+
+#[test]
+fn execution_resources_builtins_serialized_in_canonical_order() {
+    let builtins = [(Builtin::Poseidon, 3), (Builtin::Pedersen, 1), (Builtin::RangeCheck, 2)];
+    let resources = ExecutionResources {
+        builtin_instance_counter: HashMap::from(builtins.clone()),
+        ..Default::default()
+    };
+    let mut reversed_builtins = builtins;
+    reversed_builtins.reverse();
+    let other_resources = ExecutionResources {
+        builtin_instance_counter: reversed_builtins.into_iter().collect(),
+        ..Default::default()
+    };
+
+    let serialized = serde_json::to_string(&resources).unwrap();
+    assert_eq!(serialized, serde_json::to_string(&other_resources).unwrap());
+    let expected_builtins = r#"{"range_check_builtin_applications":2,"#.to_owned()
+        + r#""pedersen_builtin_applications":1,"poseidon_builtin_applications":3}"#;
+    assert!(serialized.contains(&format!(r#""builtin_instance_counter":{expected_builtins}"#)));
+    assert_eq!(
+        resources.sorted_builtin_instance_counter(),
+        other_resources.sorted_builtin_instance_counter()
+    );
+    assert_eq!(serde_json::from_str::<ExecutionResources>(&serialized).unwrap(), resources);
+}
+
+#[test]
+fn execution_resources_builtin_counts() {
+    let resources = ExecutionResources {
+        builtin_instance_counter: HashMap::from([
+            (Builtin::Pedersen, 4),
+            (Builtin::RangeCheck, 7),
+            (Builtin::Poseidon, 0),
+        ]),
+        ..Default::default()
+    };
+
+    assert_eq!(resources.builtin_count(Builtin::Pedersen), 4);
+    assert_eq!(resources.builtin_count(Builtin::RangeCheck), 7);
+    assert_eq!(resources.builtin_count(Builtin::Poseidon), 0);
+    assert_eq!(resources.builtin_count(Builtin::Keccak), 0);
+    assert_eq!(resources.total_builtin_applications(), 11);
+    assert_eq!(ExecutionResources::default().total_builtin_applications(), 0);
+
+    let saturated_resources = ExecutionResources {
+        builtin_instance_counter: HashMap::from([
+            (Builtin::Ecdsa, u64::MAX),
+            (Builtin::Bitwise, 1),
+        ]),
+        ..Default::default()
+    };
+    assert_eq!(saturated_resources.total_builtin_applications(), u64::MAX);
+}
+
+fn invoke_v3_with_resource_bounds(resource_bounds: ResourceBoundsMapping) -> Transaction {
+    Transaction::Invoke(InvokeTransaction::V3(InvokeTransactionV3 {
+        resource_bounds,
+        tip: Tip::default(),
+        signature: TransactionSignature::default(),
+        nonce: Nonce::default(),
+        sender_address: contract_address!("0x1"),
+        calldata: Calldata::default(),
+        nonce_data_availability_mode: DataAvailabilityMode::L1,
+        fee_data_availability_mode: DataAvailabilityMode::L1,
+        paymaster_data: PaymasterData::default(),
+        account_deployment_data: AccountDeploymentData::default(),
+    }))
+}
+
+#[test]
+fn max_fee_upper_bound_v1() {
+    let tx = Transaction::Invoke(InvokeTransaction::V1(InvokeTransactionV1 {
+        max_fee: Fee(1234),
+        ..Default::default()
+    }));
+    assert_eq!(tx.max_fee_upper_bound(GasPrice(1)).unwrap(), Fee(1234));
+    assert_eq!(
+        Transaction::L1Handler(L1HandlerTransaction::default())
+            .max_fee_upper_bound(GasPrice(1))
+            .unwrap(),
+        Fee(0)
+    );
+}
+
+#[test]
+fn max_fee_upper_bound_v3() {
+    let tx = invoke_v3_with_resource_bounds(ResourceBoundsMapping(
+        [
+            (
+                Resource::L1Gas,
+                ResourceBounds {
+                    max_amount: ResourceAmount(100),
+                    max_price_per_unit: ResourcePrice(30),
+                },
+            ),
+            (
+                Resource::L2Gas,
+                ResourceBounds {
+                    max_amount: ResourceAmount(5),
+                    max_price_per_unit: ResourcePrice(2),
+                },
+            ),
+        ]
+        .into(),
+    ));
+    // The L1 gas is charged at the block's price.
+    assert_eq!(tx.max_fee_upper_bound(GasPrice(20)).unwrap(), Fee(100 * 20 + 5 * 2));
+    // ... but no more than the max price per unit.
+    assert_eq!(tx.max_fee_upper_bound(GasPrice(50)).unwrap(), Fee(100 * 30 + 5 * 2));
+
+    let overflowing_tx = invoke_v3_with_resource_bounds(ResourceBoundsMapping(
+        [(
+            Resource::L1Gas,
+            ResourceBounds {
+                max_amount: ResourceAmount(u64::MAX),
+                max_price_per_unit: ResourcePrice(u128::MAX),
+            },
+        )]
+        .into(),
+    ));
+    assert_matches!(
+        overflowing_tx.max_fee_upper_bound(GasPrice(u128::MAX)),
+        Err(StarknetApiError::OutOfRange { .. })
+    );
+}
+
+#[test]
+fn inner_conversions() {
+    assert_eq!(Fee(5).into_inner(), 5);
+    assert_eq!(Fee(5).as_inner(), &5);
+    assert_eq!(u128::from(Fee(5)), 5);
+    assert_eq!(Tip(7).into_inner(), 7);
+    assert_eq!(Tip(7).as_inner(), &7);
+    assert_eq!(u64::from(Tip(7)), 7);
+    let hash = felt!("0x1234");
+    assert_eq!(TransactionHash(hash).into_inner(), hash);
+    assert_eq!(TransactionHash(hash).as_inner(), &hash);
+    assert_eq!(Felt::from(TransactionHash(hash)), hash);
+    assert_eq!(TransactionVersion::THREE.into_inner(), Felt::THREE);
+    assert_eq!(TransactionVersion::THREE.as_inner(), &Felt::THREE);
+    assert_eq!(Felt::from(TransactionVersion::ONE), Felt::ONE);
+}
+
+#[test]
+fn reverted_execution_status_new() {
+    let status = RevertedTransactionExecutionStatus::new("Out of gas".to_owned()).unwrap();
+    assert_eq!(status.revert_reason, "Out of gas");
+    assert_eq!(status.revert_reason_bytes(), b"Out of gas");
+
+    assert_matches!(
+        RevertedTransactionExecutionStatus::new("Out of gas ⛽".to_owned()),
+        Err(StarknetApiError::NonAsciiString(reason)) if reason == "Out of gas ⛽"
+    );
+    // Deserialization stays permissive, for historical data.
+    let status: RevertedTransactionExecutionStatus =
+        serde_json::from_str(r#"{"revert_reason":"Out of gas ⛽"}"#).unwrap();
+    assert_eq!(status.revert_reason_bytes(), "Out of gas ⛽".as_bytes());
+}
+
+#[test]
+fn event_content_validate_sizes() {
+    let content = EventContent {
+        keys: vec![EventKey(felt!("0x1")), EventKey(felt!("0x2"))],
+        data: EventData(vec![felt!("0x3"), felt!("0x4"), felt!("0x5")]),
+    };
+    assert!(content.validate_sizes(2, 3).is_ok());
+    assert!(content.validate_sizes(10, 10).is_ok());
+    assert!(EventContent::default().validate_sizes(0, 0).is_ok());
+
+    assert_matches!(
+        content.validate_sizes(1, 3),
+        Err(StarknetApiError::EventSizeExceeded { field: "keys", size: 2, max_size: 1 })
+    );
+    assert_matches!(
+        content.validate_sizes(2, 2),
+        Err(StarknetApiError::EventSizeExceeded { field: "data", size: 3, max_size: 2 })
+    );
+}
+
+#[test]
+fn hashed_transaction() {
+    let chain_id = ChainId::Sepolia;
+    let transaction = invoke_v3_with_resource_bounds(ResourceBoundsMapping(
+        [
+            (
+                Resource::L1Gas,
+                ResourceBounds {
+                    max_amount: ResourceAmount(10),
+                    max_price_per_unit: ResourcePrice(3),
+                },
+            ),
+            (Resource::L2Gas, ResourceBounds::default()),
+        ]
+        .into(),
+    ));
+    let hashed_transaction = HashedTransaction::new(transaction.clone(), &chain_id).unwrap();
+    assert_eq!(
+        hashed_transaction.hash(),
+        get_transaction_hash(&transaction, &chain_id, &transaction.version()).unwrap()
+    );
+    assert_eq!(hashed_transaction.transaction(), &transaction);
+    assert_eq!(hashed_transaction.into_transaction(), transaction);
+}
+
+#[test]
+fn resource_bounds_mapping_try_from_iter() {
+    let bounds = |amount| ResourceBounds {
+        max_amount: ResourceAmount(amount),
+        max_price_per_unit: ResourcePrice(1),
+    };
+    let resource_bounds_mapping = ResourceBoundsMapping::try_from_iter(
+        Resource::iter().map(|resource| (resource, bounds(resource as u64 + 1))),
+    )
+    .unwrap();
+    assert_eq!(resource_bounds_mapping.0[&Resource::L1Gas], bounds(1));
+    assert_eq!(resource_bounds_mapping.0[&Resource::L2Gas], bounds(2));
+
+    assert_matches!(
+        ResourceBoundsMapping::try_from_iter(
+            Resource::iter()
+                .filter(|resource| *resource != Resource::L2Gas)
+                .map(|resource| (resource, bounds(1)))
+        ),
+        Err(StarknetApiError::InvalidResourceMappingInitializer(_))
+    );
+}
+
+#[test]
+fn deploy_transactions_contract_address() {
+    let class_hash = class_hash!("0x3");
+    let salt = ContractAddressSalt(felt!("0x4"));
+    let calldata = calldata![felt!("0x5"), felt!("0x6")];
+    let expected_address =
+        calculate_contract_address(salt, class_hash, &calldata, ContractAddress::default())
+            .unwrap();
+
+    let deploy_tx = DeployTransaction {
+        version: TransactionVersion::ONE,
+        class_hash,
+        contract_address_salt: salt,
+        constructor_calldata: calldata.clone(),
+    };
+    assert_eq!(deploy_tx.calculate_contract_address().unwrap(), expected_address);
+
+    // The address doesn't depend on the version of the deploy account transaction.
+    let deploy_account_v1 = DeployAccountTransaction::V1(DeployAccountTransactionV1 {
+        class_hash,
+        contract_address_salt: salt,
+        constructor_calldata: calldata.clone(),
+        ..Default::default()
+    });
+    let deploy_account_v3 = DeployAccountTransaction::V3(DeployAccountTransactionV3 {
+        resource_bounds: ResourceBoundsMapping::default(),
+        tip: Tip::default(),
+        signature: TransactionSignature::default(),
+        nonce: Nonce::default(),
+        class_hash,
+        contract_address_salt: salt,
+        constructor_calldata: calldata,
+        nonce_data_availability_mode: DataAvailabilityMode::L1,
+        fee_data_availability_mode: DataAvailabilityMode::L1,
+        paymaster_data: PaymasterData::default(),
+    });
+    assert_eq!(deploy_account_v1.calculate_contract_address().unwrap(), expected_address);
+    assert_eq!(deploy_account_v3.calculate_contract_address().unwrap(), expected_address);
+}
+
+#[test]
+fn transaction_type() {
+    let transactions = [
+        (
+            Transaction::Declare(DeclareTransaction::V2(Default::default())),
+            TransactionType::Declare,
+        ),
+        (Transaction::Deploy(Default::default()), TransactionType::Deploy),
+        (
+            Transaction::DeployAccount(DeployAccountTransaction::V1(Default::default())),
+            TransactionType::DeployAccount,
+        ),
+        (Transaction::Invoke(InvokeTransaction::V1(Default::default())), TransactionType::Invoke),
+        (Transaction::L1Handler(Default::default()), TransactionType::L1Handler),
+    ];
+    for (transaction, expected_type) in transactions {
+        assert_eq!(transaction.transaction_type(), expected_type);
+    }
+
+    for transaction_type in TransactionType::iter() {
+        let serialized = serde_json::to_value(transaction_type).unwrap();
+        assert_eq!(serialized, transaction_type.to_string());
+        assert_eq!(transaction_type.to_string().parse(), Ok(transaction_type));
+    }
+    assert_eq!(TransactionType::DeployAccount.to_string(), "DEPLOY_ACCOUNT");
+    assert_eq!(TransactionType::L1Handler.to_string(), "L1_HANDLER");
+}
+
+#[test]
+fn calculate_hash_with_inferred_version() {
+    let invoke_v3 = InvokeTransactionV3 {
+        resource_bounds: ResourceBoundsMapping::try_from(vec![
+            (Resource::L1Gas, ResourceBounds::default()),
+            (Resource::L2Gas, ResourceBounds::default()),
+        ])
+        .unwrap(),
+        tip: Tip::default(),
+        signature: TransactionSignature::default(),
+        nonce: Nonce::default(),
+        sender_address: contract_address!("0x3"),
+        calldata: Calldata::default(),
+        nonce_data_availability_mode: DataAvailabilityMode::L1,
+        fee_data_availability_mode: DataAvailabilityMode::L1,
+        paymaster_data: PaymasterData::default(),
+        account_deployment_data: AccountDeploymentData::default(),
+    };
+    let transactions = [
+        (
+            Transaction::Declare(DeclareTransaction::V0(Default::default())),
+            TransactionVersion::ZERO,
+        ),
+        (Transaction::Declare(DeclareTransaction::V1(Default::default())), TransactionVersion::ONE),
+        (Transaction::Declare(DeclareTransaction::V2(Default::default())), TransactionVersion::TWO),
+        (Transaction::Deploy(Default::default()), TransactionVersion::ZERO),
+        (
+            Transaction::DeployAccount(DeployAccountTransaction::V1(Default::default())),
+            TransactionVersion::ONE,
+        ),
+        (Transaction::Invoke(InvokeTransaction::V0(Default::default())), TransactionVersion::ZERO),
+        (Transaction::Invoke(InvokeTransaction::V1(Default::default())), TransactionVersion::ONE),
+        (Transaction::Invoke(InvokeTransaction::V3(invoke_v3)), TransactionVersion::THREE),
+        (Transaction::L1Handler(Default::default()), TransactionVersion::ZERO),
+    ];
+    let chain_id = ChainId::Mainnet;
+    for (transaction, version) in transactions {
+        assert_eq!(
+            transaction.calculate_hash(&chain_id).unwrap(),
+            get_transaction_hash(&transaction, &chain_id, &version).unwrap()
+        );
+    }
+
+    // Hashing with a mismatched version silently yields a different hash.
+    let invoke_v1 = Transaction::Invoke(InvokeTransaction::V1(Default::default()));
+    assert_ne!(
+        invoke_v1.calculate_hash(&chain_id).unwrap(),
+        get_transaction_hash(&invoke_v1, &chain_id, &TransactionVersion::ZERO).unwrap()
+    );
+}
+
+#[test]
+fn transaction_variant_conversions() {
+    let invoke = InvokeTransaction::V1(InvokeTransactionV1 {
+        sender_address: contract_address!("0x1"),
+        ..Default::default()
+    });
+    let transaction = Transaction::from(invoke.clone());
+    assert_eq!(transaction, Transaction::Invoke(invoke.clone()));
+    assert_eq!(InvokeTransaction::try_from(&transaction).unwrap(), invoke);
+    assert_eq!(InvokeTransaction::try_from(transaction.clone()).unwrap(), invoke);
+
+    let mismatch = Err(StarknetApiError::UnexpectedTransactionType {
+        expected: TransactionType::L1Handler,
+        actual: TransactionType::Invoke,
+    });
+    assert_eq!(L1HandlerTransaction::try_from(&transaction), mismatch);
+    assert_eq!(L1HandlerTransaction::try_from(transaction), mismatch);
+    assert_matches!(
+        DeclareTransaction::try_from(Transaction::from(DeployTransaction::default())),
+        Err(StarknetApiError::UnexpectedTransactionType {
+            expected: TransactionType::Declare,
+            actual: TransactionType::Deploy,
+        })
+    );
+}
+
+#[test]
+fn transaction_info() {
+    let signature = TransactionSignature(vec![felt!("0x1")]);
+    let nonce = Nonce(felt!("0x2"));
+    let address = contract_address!("0x3");
+
+    let invoke_v0 = Transaction::Invoke(InvokeTransaction::V0(InvokeTransactionV0 {
+        max_fee: Fee(10),
+        signature: signature.clone(),
+        contract_address: address,
+        ..Default::default()
+    }));
+    assert_eq!(invoke_v0.signature(), Some(signature.clone()));
+    assert_eq!(invoke_v0.nonce(), None);
+    assert_eq!(invoke_v0.sender_address(), Some(address));
+    assert_eq!(TransactionInfo::max_fee(&invoke_v0), Some(Fee(10)));
+    assert_eq!(invoke_v0.resource_bounds(), None);
+    assert_eq!(invoke_v0.transaction_hash(), None);
+
+    let resource_bounds = ResourceBoundsMapping::default();
+    let invoke_v3 = Transaction::Invoke(InvokeTransaction::V3(InvokeTransactionV3 {
+        resource_bounds: resource_bounds.clone(),
+        tip: Tip::default(),
+        signature: signature.clone(),
+        nonce,
+        sender_address: address,
+        calldata: Calldata::default(),
+        nonce_data_availability_mode: DataAvailabilityMode::L1,
+        fee_data_availability_mode: DataAvailabilityMode::L1,
+        paymaster_data: PaymasterData::default(),
+        account_deployment_data: AccountDeploymentData::default(),
+    }));
+    assert_eq!(invoke_v3.nonce(), Some(nonce));
+    assert_eq!(invoke_v3.sender_address(), Some(address));
+    assert_eq!(TransactionInfo::max_fee(&invoke_v3), None);
+    assert_eq!(invoke_v3.resource_bounds(), Some(resource_bounds));
+    assert_eq!(TransactionInfo::tip(&invoke_v3), Some(Tip::default()));
+    assert_eq!(TransactionInfo::tip(&invoke_v0), None);
+
+    let declare_v2 = Transaction::Declare(DeclareTransaction::V2(DeclareTransactionV2 {
+        max_fee: Fee(20),
+        signature: signature.clone(),
+        nonce,
+        sender_address: address,
+        ..Default::default()
+    }));
+    assert_eq!(declare_v2.signature(), Some(signature.clone()));
+    assert_eq!(declare_v2.nonce(), Some(nonce));
+    assert_eq!(declare_v2.sender_address(), Some(address));
+    assert_eq!(TransactionInfo::max_fee(&declare_v2), Some(Fee(20)));
+
+    let deploy_account = Transaction::DeployAccount(DeployAccountTransaction::V1(
+        DeployAccountTransactionV1 { nonce, signature: signature.clone(), ..Default::default() },
+    ));
+    assert_eq!(deploy_account.signature(), Some(signature));
+    assert_eq!(deploy_account.nonce(), Some(nonce));
+    assert_eq!(deploy_account.sender_address(), None);
+
+    let deploy = Transaction::Deploy(DeployTransaction::default());
+    assert_eq!(deploy.signature(), None);
+    assert_eq!(deploy.nonce(), None);
+    assert_eq!(deploy.sender_address(), None);
+    assert_eq!(TransactionInfo::max_fee(&deploy), None);
+    assert_eq!(deploy.resource_bounds(), None);
+
+    let l1_handler = Transaction::L1Handler(L1HandlerTransaction { nonce, ..Default::default() });
+    assert_eq!(l1_handler.signature(), None);
+    assert_eq!(l1_handler.nonce(), Some(nonce));
+    assert_eq!(l1_handler.sender_address(), None);
+
+    let hashed_transaction = HashedTransaction::new(declare_v2.clone(), &ChainId::Sepolia).unwrap();
+    assert_eq!(hashed_transaction.transaction_hash(), Some(hashed_transaction.hash()));
+    assert_eq!(hashed_transaction.sender_address(), declare_v2.sender_address());
+}
+
+#[test]
+fn max_fee_getters() {
+    let declare_v2 = DeclareTransaction::V2(DeclareTransactionV2 {
+        max_fee: Fee(1),
+        ..Default::default()
+    });
+    assert_eq!(declare_v2.max_fee(), Some(Fee(1)));
+    let deploy_account_v1 = DeployAccountTransaction::V1(DeployAccountTransactionV1 {
+        max_fee: Fee(2),
+        ..Default::default()
+    });
+    assert_eq!(deploy_account_v1.max_fee(), Some(Fee(2)));
+    let invoke_v1 =
+        InvokeTransaction::V1(InvokeTransactionV1 { max_fee: Fee(3), ..Default::default() });
+    assert_eq!(invoke_v1.max_fee(), Some(Fee(3)));
+
+    let Transaction::Invoke(invoke_v3) =
+        invoke_v3_with_resource_bounds(ResourceBoundsMapping::default())
+    else {
+        panic!("Expected an invoke transaction.");
+    };
+    assert_eq!(invoke_v3.max_fee(), None);
+}
+
+#[test]
+fn resource_bounds_and_tip_getters() {
+    let resource_bounds = ResourceBoundsMapping(
+        [(Resource::L1Gas, ResourceBounds::default()), (Resource::L2Gas, ResourceBounds::default())]
+            .into(),
+    );
+    let Transaction::Invoke(invoke_v3) = invoke_v3_with_resource_bounds(resource_bounds.clone())
+    else {
+        panic!("Expected an invoke transaction.");
+    };
+    assert_eq!(invoke_v3.resource_bounds(), Some(resource_bounds));
+    assert_eq!(invoke_v3.tip(), Some(Tip::default()));
+
+    let invoke_v1 = InvokeTransaction::V1(InvokeTransactionV1::default());
+    assert_eq!(invoke_v1.resource_bounds(), None);
+    assert_eq!(invoke_v1.tip(), None);
+    let declare_v2 = DeclareTransaction::V2(DeclareTransactionV2::default());
+    assert_eq!(declare_v2.resource_bounds(), None);
+    assert_eq!(declare_v2.tip(), None);
+    let deploy_account_v1 = DeployAccountTransaction::V1(DeployAccountTransactionV1::default());
+    assert_eq!(deploy_account_v1.resource_bounds(), None);
+    assert_eq!(deploy_account_v1.tip(), None);
+}
+
+#[test]
+fn event_matches_filter() {
+    let key = |value: u8| EventKey(Felt::from(value));
+    let event = Event {
+        from_address: contract_address!("0x1"),
+        content: EventContent { keys: vec![key(1), key(2)], data: EventData::default() },
+    };
+
+    // Wildcards.
+    assert!(event.matches(None, &[]));
+    assert!(event.matches(None, &[vec![], vec![]]));
+    assert!(event.matches(Some(&contract_address!("0x1")), &[]));
+    assert!(!event.matches(Some(&contract_address!("0x2")), &[]));
+
+    // Exact.
+    assert!(event.matches(None, &[vec![key(1)], vec![key(2)]]));
+    assert!(event.matches(None, &[vec![key(3), key(1)], vec![key(2), key(4)]]));
+    assert!(!event.matches(None, &[vec![key(1)], vec![key(3)]]));
+
+    // Partial.
+    assert!(event.matches(None, &[vec![key(1)]]));
+    assert!(event.matches(None, &[vec![], vec![key(2)]]));
+    assert!(!event.matches(None, &[vec![key(2)]]));
+    assert!(event.matches(None, &[vec![key(1)], vec![key(2)], vec![]]));
+    assert!(!event.matches(None, &[vec![key(1)], vec![key(2)], vec![key(3)]]));
+}
+
+#[test]
+fn event_content_hash_regression() {
+    let event = Event {
+        from_address: contract_address!("0xa"),
+        content: EventContent {
+            keys: vec![EventKey(felt!("0x2")), EventKey(felt!("0x3"))],
+            data: EventData(vec![felt!("0x4"), felt!("0x5"), felt!("0x6")]),
+        },
+    };
+    let expected_hash = Poseidon::hash_array(&[
+        felt!("0xa"),
+        felt!("0x2"),
+        felt!("0x2"),
+        felt!("0x3"),
+        felt!("0x3"),
+        felt!("0x4"),
+        felt!("0x5"),
+        felt!("0x6"),
+    ]);
+    assert_eq!(event.content_hash(), expected_hash);
+    assert_eq!(
+        event.content_hash(),
+        felt!("0x1dfd08271fc30e6ce9ab16008df499b9e97dd3813a5184cc7adfcf60357b51b")
+    );
+}
+
+#[test]
+fn event_ordering() {
+    let event = |address: &str, keys: &[u8], data: &[u8]| Event {
+        from_address: contract_address!(address),
+        content: EventContent {
+            keys: keys.iter().map(|key| EventKey(Felt::from(*key))).collect(),
+            data: EventData(data.iter().map(|value| Felt::from(*value)).collect()),
+        },
+    };
+    let mut events = vec![
+        event("0x2", &[1], &[]),
+        event("0x1", &[2], &[1]),
+        event("0x1", &[1, 5], &[]),
+        event("0x1", &[1], &[9]),
+        event("0x1", &[1], &[3, 4]),
+    ];
+    let expected = vec![
+        event("0x1", &[1], &[3, 4]),
+        event("0x1", &[1], &[9]),
+        event("0x1", &[1, 5], &[]),
+        event("0x1", &[2], &[1]),
+        event("0x2", &[1], &[]),
+    ];
+
+    let mut by_sort_key = events.clone();
+    by_sort_key.sort_by(|a, b| a.sort_key().cmp(&b.sort_key()));
+    assert_eq!(by_sort_key, expected);
+    events.sort();
+    assert_eq!(events, expected);
+}
+
+#[test]
+fn event_index_ordering_and_token() {
+    let event_index = |block_number, transaction_offset, event_index| EventIndex {
+        block_number: BlockNumber(block_number),
+        transaction_offset: TransactionOffsetInBlock(transaction_offset),
+        event_index: EventIndexInTransactionOutput(event_index),
+    };
+    // Ordered across event, transaction and block boundaries.
+    assert!(event_index(1, 2, 3) < event_index(1, 2, 4));
+    assert!(event_index(1, 2, 9) < event_index(1, 3, 0));
+    assert!(event_index(1, 9, 9) < event_index(2, 0, 0));
+
+    let index = event_index(1, 2, 3);
+    assert_eq!(index.to_string(), "1-2-3");
+    assert_eq!("1-2-3".parse::<EventIndex>().unwrap(), index);
+    let json = serde_json::to_value(index).unwrap();
+    assert_eq!(json, serde_json::json!("1-2-3"));
+    assert_eq!(serde_json::from_value::<EventIndex>(json).unwrap(), index);
+
+    for token in ["", "1-2", "1-2-3-4", "1-a-3", "-1-2-3"] {
+        assert_matches!(token.parse::<EventIndex>(), Err(StarknetApiError::InvalidEventIndex(_)));
+    }
+}
+
+#[test]
+fn felt_newtypes_display() {
+    assert_eq!(ContractAddressSalt(felt!("0x1a")).to_string(), "0x1a");
+    assert_eq!(TransactionVersion::THREE.to_string(), "0x3");
+    assert_eq!(EventKey(Felt::ZERO).to_string(), "0x0");
+}
+
+#[test]
+fn parse_fee_and_tip() {
+    assert_eq!("1000".parse::<Fee>().unwrap(), Fee(1000));
+    assert_eq!("0x3e8".parse::<Fee>().unwrap(), Fee(1000));
+    assert_matches!("1e3".parse::<Fee>(), Err(StarknetApiError::ParseIntError(_)));
+
+    assert_eq!("5".parse::<Tip>().unwrap(), Tip(5));
+    assert_eq!("0xffffffffffffffff".parse::<Tip>().unwrap(), Tip(u64::MAX));
+    // Out of range for a u64.
+    assert_matches!("0x10000000000000000".parse::<Tip>(), Err(StarknetApiError::ParseIntError(_)));
+}
+
+#[test]
+fn fee_and_tip_from_ints() {
+    assert_eq!(Fee::from(u128::MAX), Fee(u128::MAX));
+    assert_eq!(Fee::from(u64::MAX), Fee(u64::MAX.into()));
+    let fee: Fee = 1000_u32.into();
+    assert_eq!(fee, Fee(1000));
+
+    assert_eq!(Tip::from(u64::MAX), Tip(u64::MAX));
+    let tip: Tip = 5_u8.into();
+    assert_eq!(tip, Tip(5));
+}
+
+#[test]
+fn execution_status_accessors() {
+    let succeeded = TransactionExecutionStatus::Succeeded;
+    assert!(succeeded.is_succeeded());
+    assert!(!succeeded.is_reverted());
+    assert_eq!(succeeded.revert_reason(), None);
+
+    let reverted = TransactionExecutionStatus::Reverted(RevertedTransactionExecutionStatus {
+        revert_reason: "Insufficient balance".to_string(),
+    });
+    assert!(!reverted.is_succeeded());
+    assert!(reverted.is_reverted());
+    assert_eq!(reverted.revert_reason(), Some("Insufficient balance"));
+}
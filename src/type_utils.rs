@@ -13,3 +13,92 @@ macro_rules! impl_from_through_intermediate {
         )+
     };
 }
+
+/// Implements access to the inner value of newtypes without going through `Deref`:
+/// - `into_inner` and `as_inner` inherent methods.
+/// - `From<newtype> for inner_type`.
+#[macro_export]
+macro_rules! impl_inner_conversions {
+    ($($newtype:ty: $inner_type:ty),+ $(,)?) => {
+        $(
+            impl $newtype {
+                /// Consumes the wrapper and returns the inner value.
+                pub fn into_inner(self) -> $inner_type {
+                    self.0
+                }
+
+                /// Returns a reference to the inner value.
+                pub fn as_inner(&self) -> &$inner_type {
+                    &self.0
+                }
+            }
+
+            impl From<$newtype> for $inner_type {
+                fn from(value: $newtype) -> Self {
+                    value.0
+                }
+            }
+        )+
+    };
+}
+
+/// Implements `Display` for felt newtypes, printing the felt as `0x`-prefixed hex.
+#[macro_export]
+macro_rules! impl_hex_display {
+    ($($newtype:ty),+ $(,)?) => {
+        $(
+            impl std::fmt::Display for $newtype {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    write!(f, "{:#x}", self.0)
+                }
+            }
+        )+
+    };
+}
+
+/// Implements `FromStr` for newtypes of unsigned integers, parsing a decimal string or a
+/// `0x`-prefixed hex string.
+#[macro_export]
+macro_rules! impl_from_dec_or_hex_str {
+    ($($newtype:ident: $int_type:ty),+ $(,)?) => {
+        $(
+            impl std::str::FromStr for $newtype {
+                type Err = $crate::StarknetApiError;
+
+                fn from_str(s: &str) -> Result<Self, Self::Err> {
+                    let value = match s.strip_prefix("0x") {
+                        Some(hex_str) => <$int_type>::from_str_radix(hex_str, 16)?,
+                        None => s.parse::<$int_type>()?,
+                    };
+                    Ok(Self(value))
+                }
+            }
+        )+
+    };
+}
+
+/// Implements `TryFrom<&str>` and `FromStr` for felt newtypes, parsing a `0x`-prefixed hex string.
+/// The felt is converted to the newtype by the given conversion, which may reject it.
+#[macro_export]
+macro_rules! impl_from_hex_str {
+    ($($newtype:ty: $from_felt:expr),+ $(,)?) => {
+        $(
+            impl TryFrom<&str> for $newtype {
+                type Error = $crate::StarknetApiError;
+
+                fn try_from(hex_str: &str) -> Result<Self, Self::Error> {
+                    let from_felt: fn($crate::StarkHash) -> Result<Self, Self::Error> = $from_felt;
+                    from_felt($crate::core::felt_from_prefixed_hex(hex_str)?)
+                }
+            }
+
+            impl std::str::FromStr for $newtype {
+                type Err = $crate::StarknetApiError;
+
+                fn from_str(hex_str: &str) -> Result<Self, Self::Err> {
+                    Self::try_from(hex_str)
+                }
+            }
+        )+
+    };
+}